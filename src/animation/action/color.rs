@@ -0,0 +1,70 @@
+use crate::animation::Interpolate;
+use crate::appearance::{GetColor, Paint, SetColor};
+use crate::arena;
+use crate::scene::Resource;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ChangeColor {
+    /// Fill and stroke both become `to`
+    SetColor {
+        from: (Paint, Paint),
+        to: Paint,
+    },
+    SetFill {
+        from: Paint,
+        to: Paint,
+    },
+    SetStroke {
+        from: (Paint, f32),
+        to: (Paint, f32),
+    },
+}
+
+impl ChangeColor {
+    pub fn init(&mut self, object: &mut arena::Object, _resource: &Resource) {
+        match self {
+            ChangeColor::SetColor { ref mut from, .. } => {
+                *from = (object.fill_paint(), object.stroke_paint());
+            }
+            ChangeColor::SetFill { ref mut from, .. } => {
+                *from = object.fill_paint();
+            }
+            ChangeColor::SetStroke { ref mut from, .. } => {
+                *from = (object.stroke_paint(), object.stroke_weight());
+            }
+        }
+    }
+    pub fn update(&mut self, object: &mut arena::Object, progress: f32) {
+        match self {
+            ChangeColor::SetColor { from, to } => {
+                object.set_fill_paint(from.0.interp(to, progress));
+                object.set_stroke_paint(from.1.interp(to, progress));
+            }
+            ChangeColor::SetFill { from, to } => {
+                object.set_fill_paint(from.interp(to, progress));
+            }
+            ChangeColor::SetStroke { from, to } => {
+                object.set_stroke_paint(from.0.interp(&to.0, progress));
+                object.set_stroke_weight(from.1.interp(&to.1, progress));
+            }
+        }
+    }
+    pub fn set_color(to: Paint) -> Self {
+        Self::SetColor {
+            from: (to.clone(), to.clone()),
+            to,
+        }
+    }
+    pub fn set_fill(to: Paint) -> Self {
+        Self::SetFill {
+            from: to.clone(),
+            to,
+        }
+    }
+    pub fn set_stroke(paint: Paint, weight: f32) -> Self {
+        Self::SetStroke {
+            from: (paint.clone(), weight),
+            to: (paint, weight),
+        }
+    }
+}
@@ -1,15 +1,25 @@
 use super::{Interpolate, TargetAction};
 
 use crate::animation::PathCompletion;
-use crate::appearance::SetOpacity;
-use crate::arena::{CircleAction, Id, Index, Object, RectangleAction, TextAction};
+use crate::appearance::{GetOpacity, Paint, SetOpacity};
+use crate::arena::{ArcAction, Arena, CircleAction, Id, Index, LineAction, Object};
+use crate::arena::{MathTexAction, RectangleAction, TextAction, ValueTrackerAction};
 use crate::consts::*;
-use crate::geom::{point, GetOrientation, GetPosition, Point, SetOrientation, SetPosition, Vector};
+use crate::geom::{point, GetDimension, GetOrientation, GetPosition, Point, SetOrientation};
+use crate::geom::{SetPosition, Vector};
+use crate::morph::Morph;
+use crate::object::Object as InnerObject;
 use crate::scene::Resource;
 
+use nannou::color::Rgb;
+
+pub use color::ChangeColor;
 pub use dimension::ChangeSize;
+pub use transform::Snapshot;
 
+pub mod color;
 pub mod dimension;
+pub mod transform;
 
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub enum Direction {
@@ -18,6 +28,40 @@ pub enum Direction {
     Left,
     Right,
 }
+
+impl Direction {
+    // Need to map direciton vector to internal enum
+    // Direction vector is used to maintain consistency in API
+    // Internally, enum makes it easier to compare
+    pub fn from_vector(direction: Vector) -> Self {
+        if direction == UP {
+            Direction::Up
+        } else if direction == DOWN {
+            Direction::Down
+        } else if direction == LEFT {
+            Direction::Left
+        } else if direction == RIGHT {
+            Direction::Right
+        } else {
+            panic!("Invalid direction specified!! Direction must be one of UP/DOWN/LEFT/RIGHT");
+        }
+    }
+    pub fn vector(&self) -> Vector {
+        match self {
+            Direction::Up => UP,
+            Direction::Down => DOWN,
+            Direction::Left => LEFT,
+            Direction::Right => RIGHT,
+        }
+    }
+    // Distance from center to edge of `object` facing this direction
+    fn extent(&self, object: &Object) -> f32 {
+        match self {
+            Direction::Up | Direction::Down => object.height() / 2.0,
+            Direction::Left | Direction::Right => object.width() / 2.0,
+        }
+    }
+}
 /// Describes the action and target object.
 /// Returns `TargetAction` which can change object instantly, or
 /// Furuther gets converted to `Animation` which contains duration and interpolation function
@@ -25,12 +69,25 @@ pub trait Actionable {
     fn move_by(&self, by: Vector) -> TargetAction;
     fn move_to(&self, to: Point) -> TargetAction;
     fn to_edge(&self, direction: Vector) -> TargetAction;
+    fn next_to<U>(&self, other: U, direction: Vector, buffer: f32) -> TargetAction
+    where
+        U: Into<Index> + Copy;
     fn show_creation(&self) -> TargetAction;
+    fn write(&self) -> TargetAction;
     fn fade_in(&self) -> TargetAction;
+    fn fade_out(&self) -> TargetAction;
+    fn scale_by(&self, by: f32) -> TargetAction;
+    fn transform<U>(&self, other: U) -> TargetAction
+    where
+        U: Into<Index> + Copy;
     fn set_width(&self, to: f32) -> TargetAction;
     fn set_height(&self, to: f32) -> TargetAction;
     fn rotate_by(&self, by: f32) -> TargetAction;
     fn rotate_to(&self, to: f32) -> TargetAction;
+    fn set_color<P: Into<Paint>>(&self, to: P) -> TargetAction;
+    fn set_fill<P: Into<Paint>>(&self, to: P) -> TargetAction;
+    fn set_stroke<P: Into<Paint>>(&self, to: P, weight: f32) -> TargetAction;
+    fn set_color_by_gradient(&self, colors: &[Rgb]) -> TargetAction;
 }
 
 impl<T> Actionable for T
@@ -46,21 +103,6 @@ where
         TargetAction::new(Id(index), Action::MoveTo { from: point(), to })
     }
     fn to_edge(&self, direction: Vector) -> TargetAction {
-        // Need to map direciton vector to internal enum
-        // Direction vector is used to maintain consistency in API
-        // Internally, enum makes it easier to compare
-        let dir_enum: Direction;
-        if direction == UP {
-            dir_enum = Direction::Up;
-        } else if direction == DOWN {
-            dir_enum = Direction::Down;
-        } else if direction == LEFT {
-            dir_enum = Direction::Left;
-        } else if direction == RIGHT {
-            dir_enum = Direction::Right;
-        } else {
-            panic!("Invalid direction specified!! Direction must be one of UP/DOWN/LEFT/RIGHT");
-        }
         let index: Index = T::into(*self);
         TargetAction::new(
             Id(index),
@@ -68,7 +110,24 @@ where
                 from: point(),
                 to: point(),
                 buffer: MED_SMALL_BUFF,
-                direction: dir_enum,
+                direction: Direction::from_vector(direction),
+            },
+        )
+    }
+    fn next_to<U>(&self, other: U, direction: Vector, buffer: f32) -> TargetAction
+    where
+        U: Into<Index> + Copy,
+    {
+        let index: Index = T::into(*self);
+        TargetAction::new(
+            Id(index),
+            Action::NextTo {
+                from: point(),
+                to: point(),
+                other: Id(other.into()),
+                anchor: point(), // Edge of other object, resolved before init
+                buffer,
+                direction: Direction::from_vector(direction),
             },
         )
     }
@@ -76,14 +135,38 @@ where
         let index: Index = T::into(*self);
         TargetAction::new(Id(index), Action::ShowCreation)
     }
+    fn write(&self) -> TargetAction {
+        let index: Index = T::into(*self);
+        TargetAction::new(Id(index), Action::Write)
+    }
     fn fade_in(&self) -> TargetAction {
         let index: Index = T::into(*self);
         TargetAction::new(Id(index), Action::FadeIn)
     }
-    // fn scale_by(&self, by: f32) -> TargetAction {
-    //     let index: Index = T::into(*self);
-    //     TargetAction::new(Id(index), Action::ChangeSize(ChangeSize::scale_by(by)))
-    // }
+    fn fade_out(&self) -> TargetAction {
+        let index: Index = T::into(*self);
+        TargetAction::new(Id(index), Action::FadeOut { from: 1.0 })
+    }
+    fn transform<U>(&self, other: U) -> TargetAction
+    where
+        U: Into<Index> + Copy,
+    {
+        let index: Index = T::into(*self);
+        TargetAction::new(
+            Id(index),
+            Action::Transform {
+                other: Id(other.into()),
+                from: Snapshot::new(),
+                to: Snapshot::new(), // Resolved from other object before init
+                target: None,
+                morph: None,
+            },
+        )
+    }
+    fn scale_by(&self, by: f32) -> TargetAction {
+        let index: Index = T::into(*self);
+        TargetAction::new(Id(index), Action::ChangeSize(ChangeSize::scale_by(by)))
+    }
     fn set_width(&self, to: f32) -> TargetAction {
         let index: Index = T::into(*self);
         TargetAction::new(Id(index), Action::ChangeSize(ChangeSize::set_width(to)))
@@ -100,6 +183,30 @@ where
         let index: Index = T::into(*self);
         TargetAction::new(Id(index), Action::RotateTo { from: 0.0, to })
     }
+    fn set_color<P: Into<Paint>>(&self, to: P) -> TargetAction {
+        let index: Index = T::into(*self);
+        TargetAction::new(
+            Id(index),
+            Action::ChangeColor(ChangeColor::set_color(to.into())),
+        )
+    }
+    fn set_fill<P: Into<Paint>>(&self, to: P) -> TargetAction {
+        let index: Index = T::into(*self);
+        TargetAction::new(
+            Id(index),
+            Action::ChangeColor(ChangeColor::set_fill(to.into())),
+        )
+    }
+    fn set_stroke<P: Into<Paint>>(&self, to: P, weight: f32) -> TargetAction {
+        let index: Index = T::into(*self);
+        TargetAction::new(
+            Id(index),
+            Action::ChangeColor(ChangeColor::set_stroke(to.into(), weight)),
+        )
+    }
+    fn set_color_by_gradient(&self, colors: &[Rgb]) -> TargetAction {
+        self.set_color(Paint::linear(colors, RIGHT))
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -121,8 +228,13 @@ pub enum Action {
     NextTo {
         from: Point,
         to: Point,
+        other: Id,
+        anchor: Point,
+        buffer: f32,
+        direction: Direction,
     },
     ChangeSize(ChangeSize),
+    ChangeColor(ChangeColor),
     RotateTo {
         from: f32,
         to: f32,
@@ -134,14 +246,60 @@ pub enum Action {
     ShowCreation,
     Write,
     FadeIn,
-    FadeOut,
-    Transform,
+    FadeOut {
+        from: f32,
+    },
+    Transform {
+        other: Id,
+        from: Snapshot,
+        to: Snapshot,
+        target: Option<Box<InnerObject>>, // Object becomes this at the end
+        morph: Option<Box<Morph>>,
+    },
     CircleAction(CircleAction),
     RectangleAction(RectangleAction),
     TextAction(TextAction),
+    MathTexAction(MathTexAction),
+    LineAction(LineAction),
+    ArcAction(ArcAction),
+    ValueTrackerAction(ValueTrackerAction),
 }
 
 impl Action {
+    /// Whether a group passes the action on to the objects in it, as groups
+    /// have no color of their own
+    pub fn applies_to_members(&self) -> bool {
+        matches!(self, Action::ChangeColor(_))
+    }
+    /// Read state of other object the action depends on. Called right before
+    /// `init`, as the other object may have changed since the action was made.
+    pub fn resolve(&mut self, objects: &Arena<Object>) {
+        match self {
+            Action::NextTo {
+                other,
+                ref mut anchor,
+                direction,
+                ..
+            } => {
+                if let Some(other) = objects.get(other.0) {
+                    let offset = direction.vector() * direction.extent(other);
+                    *anchor = other.position() + offset;
+                }
+            }
+            Action::Transform {
+                other,
+                ref mut to,
+                ref mut target,
+                ..
+            } => {
+                if let Some(other) = objects.get(other.0) {
+                    *to = Snapshot::of(other);
+                    *target = Some(Box::new(other.inner.clone()));
+                }
+            }
+            _ => (),
+        }
+    }
     pub fn init(&mut self, object: &mut Object, resource: &Resource) {
         match self {
             Action::MoveBy {
@@ -172,16 +330,58 @@ impl Action {
                 *from = object.position();
                 *to = p;
             }
+            Action::NextTo {
+                ref mut from,
+                ref mut to,
+                anchor,
+                buffer,
+                direction,
+                ..
+            } => {
+                let offset = direction.vector() * (direction.extent(object) + *buffer);
+                *from = object.position();
+                *to = *anchor + offset;
+            }
             Action::ShowCreation => {
                 object.show();
                 object.set_completion(0.0);
             }
+            Action::Write => {
+                object.show();
+                match object.inner {
+                    InnerObject::Text(ref mut text) => text.set_write_progress(Some(0.0)),
+                    InnerObject::MathTex(ref mut tex) => tex.set_write_progress(Some(0.0)),
+                    _ => object.set_completion(0.0),
+                }
+            }
             Action::FadeIn => {
                 object.set_alpha(0.0);
             }
+            Action::FadeOut { ref mut from } => {
+                *from = object.alpha();
+            }
+            Action::Transform {
+                ref mut from,
+                ref mut to,
+                target,
+                ref mut morph,
+                ..
+            } => {
+                *from = Snapshot::of(object);
+                // Target is usually never shown, so keep current opacity
+                if to.alpha <= 0.0 {
+                    to.alpha = from.alpha;
+                }
+                *morph = target
+                    .as_ref()
+                    .map(|target| Box::new(Morph::new(&object.inner, target)));
+            }
             Action::ChangeSize(action) => {
                 action.init(object, resource);
             }
+            Action::ChangeColor(action) => {
+                action.init(object, resource);
+            }
             Action::RotateTo { ref mut from, .. } => {
                 *from = object.orientation();
             }
@@ -197,6 +397,18 @@ impl Action {
             Action::TextAction(action) => {
                 action.init(object, resource);
             }
+            Action::MathTexAction(action) => {
+                action.init(object, resource);
+            }
+            Action::LineAction(action) => {
+                action.init(object, resource);
+            }
+            Action::ArcAction(action) => {
+                action.init(object, resource);
+            }
+            Action::ValueTrackerAction(action) => {
+                action.init(object, resource);
+            }
             _ => (),
         };
     }
@@ -220,15 +432,54 @@ impl Action {
                 let now = from.interp(to, progress);
                 object.move_to(now.x, now.y);
             }
+            Action::NextTo { from, to, .. } => {
+                let now = from.interp(to, progress);
+                object.move_to(now.x, now.y);
+            }
             Action::ShowCreation => {
                 object.set_completion(progress.min(1.0).max(0.0));
             }
+            Action::Write => {
+                let progress = progress.min(1.0).max(0.0);
+                // Draw normally once written
+                let write_progress = if progress < 1.0 { Some(progress) } else { None };
+                match object.inner {
+                    InnerObject::Text(ref mut text) => text.set_write_progress(write_progress),
+                    InnerObject::MathTex(ref mut tex) => tex.set_write_progress(write_progress),
+                    _ => object.set_completion(progress),
+                }
+            }
             Action::FadeIn => {
                 object.set_alpha(progress.min(1.0).max(0.0));
             }
+            Action::FadeOut { from } => {
+                // Alpha of zero hides the object at the end
+                let alpha = from.interp(&0.0, progress);
+                object.set_alpha(alpha.min(1.0).max(0.0));
+            }
+            Action::Transform {
+                from,
+                to,
+                target,
+                morph,
+                ..
+            } => {
+                match (target, morph) {
+                    (Some(target), Some(_)) if progress >= 1.0 => {
+                        object.inner = (**target).clone();
+                        object.shape = None;
+                    }
+                    (_, Some(morph)) => object.shape = Some(morph.at(progress)),
+                    _ => (),
+                }
+                from.interp(to, progress).apply(object);
+            }
             Action::ChangeSize(action) => {
                 action.update(object, progress);
             }
+            Action::ChangeColor(action) => {
+                action.update(object, progress);
+            }
             Action::RotateTo { from, to } => {
                 let deg = from.interp(to, progress);
                 object.rotate_to(deg);
@@ -247,6 +498,18 @@ impl Action {
             Action::TextAction(action) => {
                 action.update(object, progress);
             }
+            Action::MathTexAction(action) => {
+                action.update(object, progress);
+            }
+            Action::LineAction(action) => {
+                action.update(object, progress);
+            }
+            Action::ArcAction(action) => {
+                action.update(object, progress);
+            }
+            Action::ValueTrackerAction(action) => {
+                action.update(object, progress);
+            }
             _ => (),
         };
     }
@@ -0,0 +1,59 @@
+use crate::animation::Interpolate;
+use crate::appearance::{GetOpacity, SetOpacity};
+use crate::arena;
+use crate::geom::{dimension, point, Dimension, GetDimension, SetDimension};
+use crate::geom::{GetOrientation, GetPosition, Point, SetOrientation, SetPosition};
+
+/// Properties shared by all objects, which `Action::Transform` interpolates
+#[derive(Debug, Clone, PartialEq)]
+pub struct Snapshot {
+    pub position: Point,
+    pub dimension: Dimension,
+    pub orientation: f32,
+    pub alpha: f32,
+}
+
+impl Default for Snapshot {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Snapshot {
+    pub fn new() -> Self {
+        Self {
+            position: point(),
+            dimension: dimension(1.0, 1.0),
+            orientation: 0.0,
+            alpha: 1.0,
+        }
+    }
+    pub fn of(object: &arena::Object) -> Self {
+        Self {
+            position: object.position(),
+            dimension: dimension(object.width(), object.height()),
+            orientation: object.orientation(),
+            alpha: object.alpha(),
+        }
+    }
+    pub fn apply(&self, object: &mut arena::Object) {
+        object.move_to(self.position.x, self.position.y);
+        object.set_size(self.dimension.clone());
+        object.rotate_to(self.orientation);
+        object.set_alpha(self.alpha);
+    }
+}
+
+impl Interpolate for Snapshot {
+    fn interp_mut(&mut self, other: &Self, progress: f32) {
+        *self = self.interp(other, progress);
+    }
+    fn interp(&self, other: &Self, progress: f32) -> Self {
+        Self {
+            position: self.position.interp(&other.position, progress),
+            dimension: self.dimension.interp(&other.dimension, progress),
+            orientation: self.orientation.interp(&other.orientation, progress),
+            alpha: self.alpha.interp(&other.alpha, progress),
+        }
+    }
+}
@@ -1,11 +1,11 @@
 // #![allow(dead_code)]
+use crate::arena::Id;
 use crate::consts::DEFAULT_RUNTIME;
 use crate::ease::EaseType;
 use crate::scene::Scene;
 
 use super::Animation;
 use super::RunCommand;
-use super::Status;
 use super::{Action, TargetAction};
 
 pub struct AnimBuilder<'a> {
@@ -13,22 +13,22 @@ pub struct AnimBuilder<'a> {
     target_actions: Vec<TargetAction>,
     run_time: f32,
     rate_func: EaseType,
+    suspend_updaters: bool,
 }
 
 impl<'a> AnimBuilder<'a> {
     pub fn new(scene: &'a mut Scene, target_actions: Vec<TargetAction>) -> Self {
-        let mut rate_func = EaseType::Linear;
-        for ta in target_actions.iter() {
-            if ta.action == Action::ShowCreation {
-                rate_func = EaseType::Quad;
-                break;
-            }
-        }
+        let rate_func = target_actions
+            .iter()
+            .map(|ta| default_rate_func(&ta.action))
+            .find(|rate_func| *rate_func != EaseType::Linear)
+            .unwrap_or(EaseType::Linear);
         AnimBuilder {
             scene,
             target_actions,
             run_time: DEFAULT_RUNTIME,
             rate_func,
+            suspend_updaters: false,
         }
     }
     pub fn run_time(mut self, duration: f32) -> Self {
@@ -39,41 +39,42 @@ impl<'a> AnimBuilder<'a> {
         self.rate_func = rate_func;
         self
     }
+    /// Pause updaters of the animated objects until the animations end
+    pub fn suspend_updaters(mut self) -> Self {
+        self.suspend_updaters = true;
+        self
+    }
+}
+
+/// Rate used unless set, easing in and out of drawing
+pub fn default_rate_func(action: &Action) -> EaseType {
+    if *action == Action::ShowCreation || *action == Action::Write {
+        EaseType::Quad
+    } else {
+        EaseType::Linear
+    }
 }
 
 impl<'a> Drop for AnimBuilder<'a> {
     fn drop(&mut self) {
         let run_time = self.run_time;
-        let rate_func = self.rate_func;
+        let rate_func = &self.rate_func;
         // let start_time = *self.scene.commands.time_stamps().last().unwrap();
         let scene = &mut self.scene;
 
-        let mut animations: Vec<Animation> = Vec::new();
-        for ta in self.target_actions.iter() {
-            let anim = Animation {
-                object: ta.target.clone(),
-                action: ta.action.clone(),
-                run_time,
-                rate_func,
-                status: Status::NotStarted,
-            };
-            animations.push(anim);
-        }
-        // let animations = self
-        //     .target_actions
-        //     .iter()
-        //     .map(|ta| {
-        //         let object = ta.target.clone();
-        //         let action = ta.action.clone();
-        //         Animation {
-        //             object,
-        //             action,
-        //             run_time,
-        //             rate_func,
-        //             status,
-        //         }
-        //     })
-        //     .collect();
+        // Later animations of an object start from where earlier ones end
+        let mut animations: Vec<Animation> = self
+            .target_actions
+            .iter()
+            .map(|ta| ta.animate(run_time, rate_func))
+            .collect();
+        animations.sort_by(|a, b| a.delay.partial_cmp(&b.delay).unwrap());
+        let start = scene.commands.end_time();
         scene.commands.play(animations);
+        if self.suspend_updaters {
+            let targets: Vec<Id> = self.target_actions.iter().map(|ta| ta.target).collect();
+            let end = scene.commands.end_time();
+            scene.suspend_updaters(&targets, start, end);
+        }
     }
 }
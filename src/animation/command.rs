@@ -1,23 +1,25 @@
-use super::{AnimBuilder, Animation, TargetAction};
+use super::{AnimBuilder, Animation, AnimationGroup, TargetAction};
 use crate::appearance::SetOpacity;
-use crate::arena::{Arena, Id, Index, Object};
+use crate::arena::{Arena, Id, Index, NodeArena, Object, SplitText};
 use crate::scene::Resource;
 
 /// Types of commands available. Vector of `TimedCommand` constructs the
 /// sequence of motion generated by the `Scene`.
 #[derive(Debug, PartialEq)]
 pub enum Command {
-    Play(Animation),   // Play motion interpolated through specified time
-    Act(TargetAction), // Instantly takes change
-    Show(Id),          // Makes object visible
-    Remove(Id),        // Removes object from teh scene
-    Wait(f32),         // Pauses for the duration
+    Play(Animation),      // Play motion interpolated through specified time
+    Act(TargetAction),    // Instantly takes change
+    Show(Id),             // Makes object visible
+    Remove(Id),           // Removes object from teh scene
+    Attach(Id, Id),       // Makes second object a child of the first
+    Split(Id, SplitText), // Replaces text by its words and glyphs
+    Wait(f32),            // Pauses for the duration
 }
 
 impl Command {
     pub fn run_time(&self) -> f32 {
         match self {
-            Command::Play(anim) => anim.run_time,
+            Command::Play(anim) => anim.end_time(),
             Command::Wait(t) => *t,
             _ => 0.0,
         }
@@ -37,34 +39,68 @@ impl Command {
     pub fn update(&mut self, dt: f32, objects: &mut Arena<Object>, resource: &Resource) {
         match self {
             Command::Play(anim) => {
+                anim.resolve(objects);
                 objects
                     .get_mut(anim.object.0)
                     .map(|obj| anim.update(obj, dt, resource));
+                for member in anim.members.iter_mut() {
+                    if let Some(obj) = objects.get_mut(member.object.0) {
+                        member.update(obj, dt, resource);
+                    }
+                }
             }
             _ => (),
         }
     }
-    pub fn finish(&mut self, objects: &mut Arena<Object>) {
+    pub fn finish(&mut self, objects: &mut Arena<Object>, resource: &Resource) {
         match self {
             Command::Play(anim) => {
-                objects.get_mut(anim.object.0).map(|obj| anim.finish(obj));
+                anim.resolve(objects);
+                objects
+                    .get_mut(anim.object.0)
+                    .map(|obj| anim.finish(obj, resource));
+                for member in anim.members.iter_mut() {
+                    if let Some(obj) = objects.get_mut(member.object.0) {
+                        member.finish(obj, resource);
+                    }
+                }
             }
             Command::Act(ta) => {
+                ta.resolve(objects);
                 objects
                     .get_mut(ta.target.0)
-                    .map(|obj| ta.action.complete(obj));
+                    .map(|obj| ta.finish(obj, resource));
+                for id in ta.members(objects) {
+                    let mut member = TargetAction::new(id, ta.action.clone());
+                    if let Some(obj) = objects.get_mut(id.0) {
+                        member.finish(obj, resource);
+                    }
+                }
             }
             Command::Show(id) => {
                 objects.get_mut(id.0).map(|obj| obj.show());
             }
+            Command::Attach(parent, child) => {
+                objects.attach(*parent, *child);
+            }
+            Command::Split(text, split) => {
+                split.place(*text, objects);
+            }
             _ => (),
         }
     }
+    pub fn reset(&mut self) {
+        if let Command::Play(anim) = self {
+            anim.reset();
+        }
+    }
 }
 pub trait UserCommand {
     fn play(&mut self, target_action: TargetAction) -> AnimBuilder;
     fn act(&mut self, target_action: TargetAction);
     fn wait(&mut self, time: f32);
+    /// Play animations timed relative to each other, after all previous ones
+    fn play_group(&mut self, group: AnimationGroup);
     // fn new(&mut self, object: Object) -> Id;
     fn show<T>(&mut self, id: T)
     where
@@ -121,8 +157,11 @@ pub trait RunCommand {
     fn end_time(&self) -> f32;
     fn wait(&mut self, t: f32);
     fn show(&mut self, object: Id);
+    fn attach(&mut self, parent: Id, child: Id);
+    fn split(&mut self, text: Id, split: SplitText);
     fn act(&mut self, ta: TargetAction);
     fn play(&mut self, animations: Vec<Animation>);
+    fn reset(&mut self);
     fn process(
         &mut self,
         idx_start: usize,
@@ -152,11 +191,16 @@ impl RunCommand for Vec<TimedCommand> {
             .unwrap_or(idx_end + 1);
         (start_time, idx_start, idx_end)
     }
+    // Commands started together may end at different times
     fn end_time(&self) -> f32 {
-        if let Some(cmd) = self.last() {
-            cmd.event_time + cmd.inner.run_time()
-        } else {
-            0.0
+        match self.last() {
+            Some(last) => self
+                .iter()
+                .rev()
+                .take_while(|cmd| cmd.event_time == last.event_time)
+                .map(|cmd| cmd.event_time + cmd.inner.run_time())
+                .fold(last.event_time, f32::max),
+            None => 0.0,
         }
     }
     fn wait(&mut self, t: f32) {
@@ -171,6 +215,18 @@ impl RunCommand for Vec<TimedCommand> {
             inner: Command::Show(object),
         });
     }
+    fn attach(&mut self, parent: Id, child: Id) {
+        self.push(TimedCommand {
+            event_time: self.end_time(),
+            inner: Command::Attach(parent, child),
+        });
+    }
+    fn split(&mut self, text: Id, split: SplitText) {
+        self.push(TimedCommand {
+            event_time: self.end_time(),
+            inner: Command::Split(text, split),
+        });
+    }
     fn act(&mut self, ta: TargetAction) {
         self.push(TimedCommand {
             event_time: self.end_time(),
@@ -186,6 +242,9 @@ impl RunCommand for Vec<TimedCommand> {
             });
         }
     }
+    fn reset(&mut self) {
+        self.iter_mut().for_each(|cmd| cmd.inner.reset());
+    }
     fn process(
         &mut self,
         idx_prev: usize,
@@ -204,9 +263,9 @@ impl RunCommand for Vec<TimedCommand> {
         // Finish animation, or add/remove objects
         self.iter_mut()
             .skip(idx_prev)
-            .take(idx_start - idx_prev)
+            .take(idx_start.saturating_sub(idx_prev))
             .for_each(|ref mut cmd| {
-                cmd.inner.finish(objects);
+                cmd.inner.finish(objects, resource);
             });
 
         // Update animation
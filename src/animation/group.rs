@@ -0,0 +1,150 @@
+use crate::consts::DEFAULT_RUNTIME;
+use crate::ease::EaseType;
+
+use super::builder::default_rate_func;
+use super::{Animation, TargetAction};
+
+/// Part of an `AnimationGroup`, either a single action with its own timing
+/// or a nested group.
+#[derive(Debug, PartialEq)]
+pub enum Timed {
+    Action(TargetAction),
+    Group(AnimationGroup),
+}
+
+impl Timed {
+    pub fn run_time(self, duration: f32) -> Self {
+        match self {
+            Timed::Action(ta) => Timed::Action(ta.run_time(duration)),
+            Timed::Group(group) => Timed::Group(group.run_time(duration)),
+        }
+    }
+    /// Rate of a single action, groups keep the rates of their children
+    pub fn rate_func(self, rate_func: EaseType) -> Self {
+        match self {
+            Timed::Action(ta) => Timed::Action(ta.rate_func(rate_func)),
+            group => group,
+        }
+    }
+    // Duration at the natural speed of the group
+    fn duration(&self) -> f32 {
+        match self {
+            Timed::Action(ta) => ta.delay + ta.run_time.unwrap_or(DEFAULT_RUNTIME),
+            Timed::Group(group) => group.duration(),
+        }
+    }
+    // Add animations starting at `start`, with all times stretched by `scale`
+    fn schedule(self, start: f32, scale: f32, animations: &mut Vec<Animation>) {
+        match self {
+            Timed::Action(ta) => {
+                let mut animation = ta.animate(DEFAULT_RUNTIME, &default_rate_func(&ta.action));
+                animation.run_time *= scale;
+                animation.delay = start + ta.delay * scale;
+                animations.push(animation);
+            }
+            Timed::Group(group) => group.schedule(start, scale, animations),
+        }
+    }
+}
+
+impl From<TargetAction> for Timed {
+    fn from(target_action: TargetAction) -> Self {
+        Timed::Action(target_action)
+    }
+}
+
+impl From<AnimationGroup> for Timed {
+    fn from(group: AnimationGroup) -> Self {
+        Timed::Group(group)
+    }
+}
+
+/// Animations placed in time relative to each other, like manim's
+/// `AnimationGroup`. Each child starts after `lag_ratio` of the previous
+/// one has passed, so 0 plays children together and 1 one after another.
+///
+/// ```ignore
+/// let letters = split.glyphs.iter().map(|g| g.fade_in()).collect();
+/// scene.play_group(succession(vec![
+///     lagged_start(letters, 0.1).into(),
+///     Timed::from(c.move_by(RIGHT * 100.0)).run_time(2.0),
+/// ]));
+/// ```
+#[derive(Debug, PartialEq)]
+pub struct AnimationGroup {
+    children: Vec<Timed>,
+    lag_ratio: f32,
+    run_time: Option<f32>, // Stretches children to fit, if set
+}
+
+impl AnimationGroup {
+    pub fn new<T: Into<Timed>>(children: Vec<T>, lag_ratio: f32) -> Self {
+        Self {
+            children: children.into_iter().map(|child| child.into()).collect(),
+            lag_ratio: lag_ratio.max(0.0),
+            run_time: None,
+        }
+    }
+    /// Play the whole group in `duration`, keeping the timing of children
+    /// relative to each other
+    pub fn run_time(mut self, duration: f32) -> Self {
+        self.run_time = Some(duration.max(0.0));
+        self
+    }
+    pub fn lag_ratio(mut self, lag_ratio: f32) -> Self {
+        self.lag_ratio = lag_ratio.max(0.0);
+        self
+    }
+    pub fn duration(&self) -> f32 {
+        self.run_time.unwrap_or_else(|| self.natural_duration())
+    }
+    fn natural_duration(&self) -> f32 {
+        let mut start = 0.0;
+        let mut end: f32 = 0.0;
+        for child in self.children.iter() {
+            let duration = child.duration();
+            end = end.max(start + duration);
+            start += duration * self.lag_ratio;
+        }
+        end
+    }
+    /// Animations of all children, with delays relative to the start of
+    /// the group, in order of their start
+    pub fn animations(self) -> Vec<Animation> {
+        let mut animations = Vec::new();
+        self.schedule(0.0, 1.0, &mut animations);
+        // Later animations of an object start from where earlier ones end
+        animations.sort_by(|a, b| a.delay.partial_cmp(&b.delay).unwrap());
+        animations
+    }
+    fn schedule(self, start: f32, scale: f32, animations: &mut Vec<Animation>) {
+        let natural = self.natural_duration();
+        let scale = match self.run_time {
+            Some(run_time) if natural > 0.0 => scale * run_time / natural,
+            _ => scale,
+        };
+        let lag_ratio = self.lag_ratio;
+        let mut start = start;
+        for child in self.children.into_iter() {
+            let duration = child.duration() * scale;
+            child.schedule(start, scale, animations);
+            start += duration * lag_ratio;
+        }
+    }
+}
+
+/// Children played together
+pub fn animation_group<T: Into<Timed>>(children: Vec<T>) -> AnimationGroup {
+    AnimationGroup::new(children, 0.0)
+}
+
+/// Children played one after another, like manim's `Succession`
+pub fn succession<T: Into<Timed>>(children: Vec<T>) -> AnimationGroup {
+    AnimationGroup::new(children, 1.0)
+}
+
+/// Children starting one after another while the previous ones are still
+/// running, like manim's `LaggedStart`
+pub fn lagged_start<T: Into<Timed>>(children: Vec<T>, lag_ratio: f32) -> AnimationGroup {
+    AnimationGroup::new(children, lag_ratio)
+}
@@ -2,14 +2,18 @@
 pub use self::action::{Action, Actionable, ChangeSize, Direction};
 pub use self::builder::AnimBuilder;
 pub use self::command::{Command, RunCommand, TimedCommand, UserCommand};
+pub use self::group::{animation_group, lagged_start, succession, AnimationGroup, Timed};
+pub use self::updater::{UpdateFn, Updater, UpdaterId};
 
-use crate::arena::{Id, Object};
+use crate::arena::{Arena, Id, NodeArena, Object};
 use crate::ease::EaseType;
 use crate::scene::Resource;
 
 pub mod action;
 pub mod builder;
 pub mod command;
+pub mod group;
+pub mod updater;
 
 pub fn lerp(from: f32, to: f32, p: f32) -> f32 {
     from * (1.0 - p) + to * (p)
@@ -42,17 +46,73 @@ impl Interpolate for u32 {
     }
 }
 
+/// Action on an object, with optional timing of its own when played
+/// alongside others.
+///
+/// ```ignore
+/// scene.play_many(vec![
+///     a.move_by(RIGHT * 100.0).run_time(0.5),
+///     b.fade_in().delay(0.5).rate_func(EaseType::Elastic),
+/// ]);
+/// ```
 #[derive(Debug, PartialEq)]
 pub struct TargetAction {
     pub target: Id,
     pub action: Action,
+    pub run_time: Option<f32>,       // Run time of the batch if not set
+    pub rate_func: Option<EaseType>, // Rate of the batch if not set
+    pub delay: f32,                  // From the start of the batch
 }
 
 impl TargetAction {
     pub fn new(target: Id, action: Action) -> Self {
-        Self { target, action }
+        Self {
+            target,
+            action,
+            run_time: None,
+            rate_func: None,
+            delay: 0.0,
+        }
+    }
+    pub fn run_time(mut self, duration: f32) -> Self {
+        self.run_time = Some(duration.max(0.0));
+        self
+    }
+    pub fn rate_func(mut self, rate_func: EaseType) -> Self {
+        self.rate_func = Some(rate_func);
+        self
     }
-    pub fn finish(&mut self, object: &mut Object) {
+    pub fn delay(mut self, delay: f32) -> Self {
+        self.delay = delay.max(0.0);
+        self
+    }
+    // Animation of the action, timed by its own settings over the defaults
+    fn animate(&self, run_time: f32, rate_func: &EaseType) -> Animation {
+        Animation {
+            object: self.target.clone(),
+            action: self.action.clone(),
+            run_time: self.run_time.unwrap_or(run_time),
+            rate_func: self.rate_func.clone().unwrap_or_else(|| rate_func.clone()),
+            delay: self.delay,
+            status: Status::NotStarted,
+            members: Vec::new(),
+        }
+    }
+    // Read other objects the action depends on
+    pub fn resolve(&mut self, objects: &Arena<Object>) {
+        self.action.resolve(objects);
+    }
+    /// Objects inside a target group that the action is passed on to
+    pub fn members(&self, objects: &Arena<Object>) -> Vec<Id> {
+        match objects.get(self.target.0) {
+            Some(object) if object.is_group() && self.action.applies_to_members() => {
+                objects.descendants(self.target)
+            }
+            _ => Vec::new(),
+        }
+    }
+    pub fn finish(&mut self, object: &mut Object, resource: &Resource) {
+        self.action.init(object, resource);
         self.action.complete(object);
     }
 }
@@ -83,7 +143,9 @@ pub struct Animation {
     action: Action,
     run_time: f32,
     rate_func: EaseType,
+    delay: f32, // Start after the start of the command
     status: Status,
+    members: Vec<Animation>, // Same animation on objects of a target group
 }
 
 impl Animation {
@@ -96,17 +158,50 @@ impl Animation {
             action,
             rate_func,
             run_time,
+            delay: 0.0,
             status,
+            members: Vec::new(),
         }
     }
+    /// Time from the start of the command to the end of the animation
+    pub fn end_time(&self) -> f32 {
+        self.delay + self.run_time
+    }
     // Set object to final state in animation
-    pub fn finish(&mut self, object: &mut Object) {
+    pub fn finish(&mut self, object: &mut Object, resource: &Resource) {
         if !(self.status == Status::Complete) {
-            // let object = &mut self.object;
+            // Animation may be skipped over entirely when seeking
+            if self.status == Status::NotStarted {
+                self.init(object, resource);
+            }
             self.action.update(object, 1.0);
             self.status = Status::Complete;
         }
     }
+    // Read other objects the action depends on, right before initializing
+    pub fn resolve(&mut self, objects: &Arena<Object>) {
+        if self.status == Status::NotStarted {
+            self.action.resolve(objects);
+            let target = TargetAction::new(self.object, self.action.clone());
+            self.members = target
+                .members(objects)
+                .into_iter()
+                .map(|object| Animation {
+                    object,
+                    action: self.action.clone(),
+                    run_time: self.run_time,
+                    rate_func: self.rate_func.clone(),
+                    delay: self.delay,
+                    status: Status::NotStarted,
+                    members: Vec::new(),
+                })
+                .collect();
+        }
+    }
+    // Forget progress so that animation is initialized again on next update
+    pub fn reset(&mut self) {
+        self.status = Status::NotStarted;
+    }
     // Initialize animation state with current object state
     fn init(&mut self, object: &mut Object, resource: &Resource) {
         self.action.init(object, resource);
@@ -121,19 +216,27 @@ impl Animation {
     }
     // Update animation status and time
     fn update_status(&mut self, object: &mut Object, t: f32, resource: &Resource) {
-        if t > 0.0 {
-            if self.status == Status::NotStarted {
-                self.action.init(object, resource);
-            }
-            self.status = Status::Animating(t / self.run_time);
+        // Initialize even at t = 0, as frames can land exactly on the start time
+        if self.status == Status::NotStarted {
+            self.init(object, resource);
         }
+        self.status = Status::Animating(t / self.run_time);
     }
-    // Main update function for progressing through animation
+    // Main update function for progressing through animation, `t` being
+    // the time since the start of the command
     pub fn update(&mut self, object: &mut Object, t: f32, resource: &Resource) {
-        let t = t.min(self.run_time);
+        // Delayed animations leave the object alone until they start
+        if t < self.delay {
+            return;
+        }
+        let t = (t - self.delay).min(self.run_time);
 
         self.update_status(object, t, resource);
-        let p = self.rate_func.calculate(t / self.run_time);
+        let p = if self.run_time > 0.0 {
+            self.rate_func.calculate(t / self.run_time)
+        } else {
+            1.0
+        };
         // let object = &mut self.object;
 
         self.action.update(object, p);
@@ -145,7 +248,7 @@ mod tests {
     use super::*;
     use crate::consts::*;
     use crate::object::circle::circle;
-    use crate::scene::scene;
+    use crate::scene::{scene, Scene};
     use nannou::geom::{Range, Rect};
     #[test]
     fn simple_shift() {
@@ -169,4 +272,129 @@ mod tests {
         //     .run_time(1.0)
         //     .rate_func(BOUNCE);
     }
+
+    #[test]
+    fn seek_any_order() {
+        use crate::animation::{Actionable, UserCommand};
+        use crate::arena::{AddObject, HasArena};
+        use crate::geom::{point_at, GetPosition};
+
+        let mut scene = scene(Rect::from_w_h(640.0, 480.0));
+        let c = scene.circle();
+        scene.act(c.move_to(point_at(10.0, 0.0)));
+        scene.play(c.move_by(RIGHT * 100.0)).run_time(1.0);
+        scene.play(c.move_by(UP * 100.0)).run_time(1.0);
+        let id = Id(c.0);
+
+        // Jumping straight past both animations
+        scene.update(3.0);
+        assert_eq!(scene.get(&id).unwrap().position(), point_at(110.0, 100.0));
+
+        // Going backwards replays from the start
+        scene.update(0.5);
+        assert_eq!(scene.get(&id).unwrap().position(), point_at(60.0, 0.0));
+
+        // Frames landing exactly on start times
+        scene.update(1.0);
+        assert_eq!(scene.get(&id).unwrap().position(), point_at(110.0, 0.0));
+
+        // Same time always gives the same state
+        let x = |scene: &mut Scene, t| scene.state_at(t).get(id.0).unwrap().position().x;
+        assert_eq!(x(&mut scene, 0.25), x(&mut scene, 0.25));
+        assert_eq!(x(&mut scene, 0.25), 35.0);
+    }
+
+    #[test]
+    fn next_to_and_transform() {
+        use crate::animation::{Actionable, UserCommand};
+        use crate::appearance::GetOpacity;
+        use crate::arena::{AddObject, HasArena};
+        use crate::geom::{point_at, GetDimension, GetPosition};
+
+        let mut scene = scene(Rect::from_w_h(640.0, 480.0));
+        let a = scene.rectangle();
+        let b = scene.rectangle();
+        scene.act(a.show_creation());
+        scene.act(b.move_to(point_at(100.0, 50.0)));
+        scene.act(b.set_width(40.0));
+        scene.play(a.next_to(b, RIGHT, 10.0));
+        scene.play(a.transform(b));
+        scene.play(a.fade_out());
+        let (a, b) = (Id(a.0), Id(b.0));
+
+        scene.update(1.0);
+        let width = scene.get(&a).unwrap().width();
+        let expected = point_at(100.0 + 20.0 + 10.0 + width / 2.0, 50.0);
+        assert_eq!(scene.get(&a).unwrap().position(), expected);
+
+        // Target was never shown, so opacity is kept
+        scene.update(2.0);
+        let object = scene.get(&a).unwrap();
+        assert_eq!(object.position(), scene.get(&b).unwrap().position());
+        assert_eq!(object.width(), 40.0);
+        assert_eq!(object.alpha(), 1.0);
+
+        scene.update(3.0);
+        assert!(!scene.get(&a).unwrap().is_visible());
+    }
+
+    #[test]
+    fn groups_lag_their_children() {
+        use crate::animation::{Actionable, UserCommand};
+        use crate::arena::{AddObject, HasArena};
+        use crate::geom::{point_at, GetPosition};
+
+        let mut scene = scene(Rect::from_w_h(640.0, 480.0));
+        let a = scene.circle();
+        let b = scene.circle();
+        let moves = vec![a.move_by(RIGHT * 100.0), b.move_by(RIGHT * 100.0)];
+        scene.play_group(succession(vec![
+            lagged_start(moves, 0.5).into(),
+            Timed::from(a.move_by(UP * 100.0)).run_time(2.0),
+        ]));
+        scene.wait(1.0);
+        let (a, b) = (Id(a.0), Id(b.0));
+        assert_eq!(scene.commands.end_time(), 3.5 + 1.0);
+
+        // Second move starts halfway through the first
+        scene.update(1.0);
+        assert_eq!(scene.get(&a).unwrap().position(), point_at(100.0, 0.0));
+        assert_eq!(scene.get(&b).unwrap().position(), point_at(50.0, 0.0));
+
+        // Last move waits for the lagged group to end
+        scene.update(1.5);
+        assert_eq!(scene.get(&a).unwrap().position(), point_at(100.0, 0.0));
+        scene.update(2.5);
+        assert_eq!(scene.get(&a).unwrap().position(), point_at(100.0, 50.0));
+        assert_eq!(scene.get(&b).unwrap().position(), point_at(100.0, 0.0));
+    }
+
+    #[test]
+    fn batch_with_own_timings() {
+        use crate::animation::{Actionable, UserCommand};
+        use crate::arena::{AddObject, HasArena};
+        use crate::geom::{point_at, GetPosition};
+
+        let mut scene = scene(Rect::from_w_h(640.0, 480.0));
+        let a = scene.circle();
+        let b = scene.circle();
+        scene
+            .play_many(vec![
+                a.move_by(RIGHT * 100.0).run_time(0.5),
+                b.move_by(RIGHT * 100.0).delay(1.0),
+            ])
+            .run_time(2.0);
+        scene.play(a.move_by(UP * 100.0));
+        let (a, b) = (Id(a.0), Id(b.0));
+        assert_eq!(scene.commands.end_time(), 3.0 + 1.0);
+
+        scene.update(1.5);
+        assert_eq!(scene.get(&a).unwrap().position(), point_at(100.0, 0.0));
+        assert_eq!(scene.get(&b).unwrap().position(), point_at(25.0, 0.0));
+
+        // Next command waits for the slowest animation
+        scene.update(3.5);
+        assert_eq!(scene.get(&a).unwrap().position(), point_at(100.0, 50.0));
+        assert_eq!(scene.get(&b).unwrap().position(), point_at(100.0, 0.0));
+    }
 }
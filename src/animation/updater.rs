@@ -0,0 +1,69 @@
+use crate::arena::{Id, Object};
+use crate::scene::Scene;
+
+use std::fmt;
+use std::rc::Rc;
+
+/// Closure run on an object every frame, given the scene to read other
+/// objects from and the time since the previous frame
+pub type UpdateFn = dyn Fn(&mut Object, &Scene, f32);
+
+/// Handle to remove an updater
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct UpdaterId(pub usize);
+
+/// Per-frame change of an object, like manim's `add_updater`. Updaters are
+/// placed on the timeline like commands, running from when they are added
+/// until they are removed, except while suspended.
+#[derive(Clone)]
+pub struct Updater {
+    pub(crate) id: UpdaterId,
+    pub(crate) target: Id,
+    pub(crate) func: Rc<UpdateFn>,
+    start: f32,
+    end: Option<f32>,
+    suspended: Vec<(f32, f32)>,
+}
+
+impl fmt::Debug for Updater {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Updater")
+            .field("id", &self.id)
+            .field("target", &self.target)
+            .field("start", &self.start)
+            .field("end", &self.end)
+            .field("suspended", &self.suspended)
+            .finish()
+    }
+}
+
+impl Updater {
+    pub fn new(id: UpdaterId, target: Id, func: Rc<UpdateFn>, start: f32) -> Self {
+        Self {
+            id,
+            target,
+            func,
+            start,
+            end: None,
+            suspended: Vec::new(),
+        }
+    }
+    /// Stop running from `time` on
+    pub fn remove_at(&mut self, time: f32) {
+        self.end = Some(time.max(self.start));
+    }
+    /// Pause between `from` and `to`, as while its object is animated
+    pub fn suspend(&mut self, from: f32, to: f32) {
+        if from < to {
+            self.suspended.push((from, to));
+        }
+    }
+    pub fn is_active(&self, time: f32) -> bool {
+        time >= self.start
+            && self.end.map_or(true, |end| time < end)
+            && !self
+                .suspended
+                .iter()
+                .any(|(from, to)| *from <= time && time < *to)
+    }
+}
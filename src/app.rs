@@ -1,31 +1,235 @@
 use nannou::prelude::*;
 
-use crate::scene::{self, Construct, Scene};
+use crate::animation::RunCommand;
+use crate::consts::{DEFAULT_WINDOW_HEIGHT, DEFAULT_WINDOW_WIDTH};
+use crate::playback::{cut_points, Playback};
+use crate::render::{self, PngSequence, RenderSettings, VideoEncoder};
+use crate::scene::Scene;
 
 use nannou::lyon::algorithms::path::math::Point;
 use nannou::lyon::algorithms::path::PathSlice;
 use nannou::lyon::algorithms::walk::{walk_along_path, RegularPattern};
 use nannou::lyon::path::iterator::*;
 
-pub fn run() {
-    nannou::app(scene).update(update).view(view).run();
+use std::cell::RefCell;
+use std::io;
+use std::path::Path;
+
+thread_local! {
+    // nannou only accepts plain function pointers, so the scene to preview
+    // is handed over to `model` through here.
+    static PREVIEW: RefCell<Option<Model>> = RefCell::new(None);
+}
+
+struct Model {
+    scene: Scene,
+    settings: RenderSettings,
+    playback: Playback,
+    cuts: Vec<f32>,
+    scrubbing: bool,
+    show_timeline: bool,
+}
+
+pub fn default_settings() -> RenderSettings {
+    RenderSettings::new(DEFAULT_WINDOW_WIDTH, DEFAULT_WINDOW_HEIGHT)
+}
+
+/// Open a window and play `scene` within the time range of `settings`.
+///
+/// Controls:
+/// - `Space`: pause / resume
+/// - `Left` / `Right`: step one frame backward / forward
+/// - `Up` / `Down`: jump to previous / next cut point
+/// - `-` / `=` / `0`: slower / faster / normal speed
+/// - `I` / `O` / `C`: set loop in / set loop out / clear loop points
+/// - `L`: toggle looping, `R` or `Home`: restart, `H`: hide timeline
+/// - Click or drag on the timeline bar to scrub
+pub fn preview(scene: Scene, settings: RenderSettings) {
+    let end = settings.end.unwrap_or_else(|| scene.commands.end_time());
+    let playback = Playback::new(settings.start, end);
+    let cuts = cut_points(&scene.commands.time_stamps());
+    let state = Model {
+        scene,
+        settings,
+        playback,
+        cuts,
+        scrubbing: false,
+        show_timeline: true,
+    };
+    PREVIEW.with(|preview| *preview.borrow_mut() = Some(state));
+    nannou::app(model).update(update).view(view).run();
+}
+
+/// Render `scene` offline into numbered PNG files in `directory`,
+/// without opening a window. Returns the number of frames written.
+pub fn render_png<P: AsRef<Path>>(
+    scene: &mut Scene,
+    settings: &RenderSettings,
+    directory: P,
+) -> io::Result<usize> {
+    let mut sink = PngSequence::new(directory)?;
+    render::render(scene, settings, &mut sink)
+}
+
+/// Render `scene` offline and encode it into a video file with ffmpeg.
+/// Codec is chosen from the extension of `output` (e.g. `.mp4`, `.webm`).
+pub fn render_video<P: AsRef<Path>>(
+    scene: &mut Scene,
+    settings: &RenderSettings,
+    output: P,
+) -> io::Result<usize> {
+    let mut sink = VideoEncoder::new(output, settings);
+    render::render(scene, settings, &mut sink)
+}
+
+/// Export `scene` as SVG. An `output` ending with `.svg` gets the single
+/// frame at the start time, otherwise every frame is written into `output`.
+pub fn render_svg<P: AsRef<Path>>(
+    scene: &mut Scene,
+    settings: &RenderSettings,
+    output: P,
+) -> io::Result<usize> {
+    let output = output.as_ref();
+    if output.extension().map_or(false, |ext| ext == "svg") {
+        render::svg_frame(scene, settings, settings.start, output)?;
+        Ok(1)
+    } else {
+        render::svg_sequence(scene, settings, output)
+    }
+}
+
+fn model(app: &App) -> Model {
+    let model = PREVIEW
+        .with(|preview| preview.borrow_mut().take())
+        .expect("Preview started without a scene");
+    app.new_window()
+        .size(model.settings.width, model.settings.height)
+        .view(view)
+        .key_pressed(key_pressed)
+        .mouse_pressed(mouse_pressed)
+        .mouse_released(mouse_released)
+        .mouse_moved(mouse_moved)
+        .build()
+        .unwrap();
+    model
+}
+
+fn update(_app: &App, model: &mut Model, update: Update) {
+    model.playback.advance(update.since_last.as_secs_f32());
+    model.scene.update(model.playback.time());
+}
+
+fn key_pressed(_app: &App, model: &mut Model, key: Key) {
+    let fps = model.settings.fps;
+    let playback = &mut model.playback;
+    match key {
+        Key::Space => playback.toggle_pause(),
+        Key::Right => playback.step(1, fps),
+        Key::Left => playback.step(-1, fps),
+        Key::Down => playback.next_cut(&model.cuts),
+        Key::Up => playback.previous_cut(&model.cuts),
+        Key::Equals | Key::Add => playback.faster(),
+        Key::Minus | Key::Subtract => playback.slower(),
+        Key::Key0 => playback.reset_speed(),
+        Key::I => playback.set_loop_in(),
+        Key::O => playback.set_loop_out(),
+        Key::C => playback.clear_loop(),
+        Key::L => playback.toggle_loop(),
+        Key::R | Key::Home => playback.restart(),
+        Key::H => model.show_timeline = !model.show_timeline,
+        _ => (),
+    }
+}
+
+fn mouse_pressed(app: &App, model: &mut Model, button: MouseButton) {
+    let bar = timeline(app.window_rect());
+    let on_bar = (app.mouse.y - bar.y()).abs() < TIMELINE_GRAB
+        && app.mouse.x >= bar.left() - TIMELINE_GRAB
+        && app.mouse.x <= bar.right() + TIMELINE_GRAB;
+    if button == MouseButton::Left && model.show_timeline && on_bar {
+        model.scrubbing = true;
+        scrub(app, model);
+    }
 }
 
-fn scene<'a>(app: &App) -> Scene {
-    app.new_window().size(640, 480).view(view).build().unwrap();
-    let win_rect = app.main_window().rect();
+fn mouse_released(_app: &App, model: &mut Model, _button: MouseButton) {
+    model.scrubbing = false;
+}
 
-    let mut scene = scene::scene(win_rect);
-    scene.construct();
-    scene
+fn mouse_moved(app: &App, model: &mut Model, _position: Point2) {
+    if model.scrubbing {
+        scrub(app, model);
+    }
 }
 
-fn update(app: &App, scene: &mut Scene, _update: Update) {
-    scene.update(app.time);
+fn scrub(app: &App, model: &mut Model) {
+    let bar = timeline(app.window_rect());
+    model.playback.scrub((app.mouse.x - bar.left()) / bar.w());
+}
+
+const TIMELINE_MARGIN: f32 = 20.0;
+const TIMELINE_HEIGHT: f32 = 6.0;
+const TIMELINE_GRAB: f32 = 10.0; // Distance from the bar that still grabs it
+
+// Area of the timeline bar at the bottom of the window
+fn timeline(win: Rect) -> Rect {
+    Rect::from_x_y_w_h(
+        win.x(),
+        win.bottom() + TIMELINE_MARGIN,
+        win.w() - TIMELINE_MARGIN * 2.0,
+        TIMELINE_HEIGHT,
+    )
+}
+
+fn draw_timeline(draw: &nannou::Draw, win: Rect, model: &Model) {
+    let bar = timeline(win);
+    let playback = &model.playback;
+    let duration = (playback.end() - playback.start()).max(std::f32::EPSILON);
+    let x_at = |t: f32| bar.left() + bar.w() * (t - playback.start()) / duration;
+
+    draw.rect()
+        .x_y(bar.x(), bar.y())
+        .w_h(bar.w(), bar.h())
+        .rgba(1.0, 1.0, 1.0, 0.15);
+    if playback.is_looping() {
+        let (loop_in, loop_out) = playback.loop_range();
+        let (left, right) = (x_at(loop_in), x_at(loop_out));
+        draw.rect()
+            .x_y((left + right) / 2.0, bar.y())
+            .w_h(right - left, bar.h())
+            .rgba(0.3, 0.6, 1.0, 0.4);
+    }
+    let progress = x_at(playback.time()) - bar.left();
+    draw.rect()
+        .x_y(bar.left() + progress / 2.0, bar.y())
+        .w_h(progress, bar.h())
+        .rgba(1.0, 1.0, 1.0, 0.6);
+    for cut in model.cuts.iter() {
+        let x = x_at(*cut);
+        draw.line()
+            .start(pt2(x, bar.bottom() - 3.0))
+            .end(pt2(x, bar.top() + 3.0))
+            .weight(1.0)
+            .rgba(1.0, 1.0, 1.0, 0.8);
+    }
+
+    let mut status = format!("{:.2}s  x{}", playback.time(), playback.speed());
+    if playback.is_paused() {
+        status.push_str("  paused");
+    }
+    if playback.is_looping() {
+        status.push_str("  loop");
+    }
+    draw.text(&status)
+        .x_y(bar.x(), bar.top() + 12.0)
+        .w_h(bar.w(), 14.0)
+        .font_size(12)
+        .left_justify()
+        .rgba(1.0, 1.0, 1.0, 0.8);
 }
 
-fn view(app: &App, scene: &Scene, frame: Frame) {
-    let draw = app.draw();
+fn view(app: &App, model: &Model, frame: Frame) {
+    let mut draw = app.draw();
     draw.background().color(BLACK);
 
     // let win_rect = app.main_window().rect().pad_left(20.0);
@@ -83,7 +287,10 @@ fn view(app: &App, scene: &Scene, frame: Frame) {
     //     }
     // });
 
-    scene.draw(draw.clone());
+    model.scene.draw(&mut draw);
+    if model.show_timeline {
+        draw_timeline(&draw, app.window_rect(), model);
+    }
     // println!("{}", app.fps());
     draw.to_frame(app, &frame).unwrap();
 }
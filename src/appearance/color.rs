@@ -0,0 +1,126 @@
+use super::Paint;
+use crate::animation::{lerp, Interpolate};
+use crate::consts::DEFAULT_STROKE_WEIGHT;
+
+use nannou::color::Rgb;
+
+pub trait GetColor {
+    /// Fill color, or representative color of a gradient fill
+    fn color(&self) -> Rgb;
+    fn stroke_color(&self) -> Rgb;
+    fn stroke_weight(&self) -> f32 {
+        DEFAULT_STROKE_WEIGHT
+    }
+    fn fill_paint(&self) -> Paint {
+        Paint::Solid(self.color())
+    }
+    fn stroke_paint(&self) -> Paint {
+        Paint::Solid(self.stroke_color())
+    }
+}
+
+pub trait SetColor: GetColor {
+    fn set_fill_paint(&mut self, paint: Paint);
+    fn set_stroke_paint(&mut self, paint: Paint);
+    fn set_stroke_weight(&mut self, weight: f32);
+    /// Set fill color
+    fn set_color(&mut self, color: Rgb) {
+        self.set_fill_paint(Paint::Solid(color));
+    }
+    fn set_stroke_color(&mut self, color: Rgb) {
+        self.set_stroke_paint(Paint::Solid(color));
+    }
+}
+
+// Colors are interpolated in OKLab, so that the midpoint of two colors has
+// the lightness and hue one would expect, instead of the muddy or dark
+// midpoints of interpolating sRGB components directly
+impl Interpolate for Rgb {
+    fn interp_mut(&mut self, other: &Self, progress: f32) {
+        *self = self.interp(other, progress);
+    }
+    fn interp(&self, other: &Self, progress: f32) -> Self {
+        let a = to_oklab(self);
+        let b = to_oklab(other);
+        from_oklab([
+            lerp(a[0], b[0], progress),
+            lerp(a[1], b[1], progress),
+            lerp(a[2], b[2], progress),
+        ])
+    }
+}
+
+fn to_linear(c: f32) -> f32 {
+    if c <= 0.04045 {
+        c / 12.92
+    } else {
+        ((c + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+fn to_srgb(c: f32) -> f32 {
+    let c = c.min(1.0).max(0.0);
+    if c <= 0.003_130_8 {
+        c * 12.92
+    } else {
+        1.055 * c.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+fn to_oklab(color: &Rgb) -> [f32; 3] {
+    let r = to_linear(color.red);
+    let g = to_linear(color.green);
+    let b = to_linear(color.blue);
+
+    let l = (0.412_221_47 * r + 0.536_332_55 * g + 0.051_445_995 * b).cbrt();
+    let m = (0.211_903_5 * r + 0.680_699_5 * g + 0.107_396_96 * b).cbrt();
+    let s = (0.088_302_46 * r + 0.281_718_85 * g + 0.629_978_7 * b).cbrt();
+
+    [
+        0.210_454_26 * l + 0.793_617_8 * m - 0.004_072_047 * s,
+        1.977_998_5 * l - 2.428_592_2 * m + 0.450_593_7 * s,
+        0.025_904_037 * l + 0.782_771_77 * m - 0.808_675_77 * s,
+    ]
+}
+
+fn from_oklab(lab: [f32; 3]) -> Rgb {
+    let l = lab[0] + 0.396_337_78 * lab[1] + 0.215_803_76 * lab[2];
+    let m = lab[0] - 0.105_561_346 * lab[1] - 0.063_854_17 * lab[2];
+    let s = lab[0] - 0.089_484_18 * lab[1] - 1.291_485_5 * lab[2];
+    let (l, m, s) = (l * l * l, m * m * m, s * s * s);
+
+    Rgb::new(
+        to_srgb(4.076_741_7 * l - 3.307_711_6 * m + 0.230_969_94 * s),
+        to_srgb(-1.268_438 * l + 2.609_757_4 * m - 0.341_319_38 * s),
+        to_srgb(-0.004_196_086_3 * l - 0.703_418_6 * m + 1.707_614_7 * s),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn close(a: &Rgb, b: &Rgb) -> bool {
+        (a.red - b.red).abs() < 1e-3
+            && (a.green - b.green).abs() < 1e-3
+            && (a.blue - b.blue).abs() < 1e-3
+    }
+
+    #[test]
+    fn interp_keeps_endpoints() {
+        let red = Rgb::new(1.0, 0.0, 0.0);
+        let blue = Rgb::new(0.0, 0.0, 1.0);
+        assert!(close(&red.interp(&blue, 0.0), &red));
+        assert!(close(&red.interp(&blue, 1.0), &blue));
+    }
+
+    #[test]
+    fn interp_is_perceptual() {
+        // Midpoint of black and white is perceptual mid gray, not 0.5 sRGB
+        let black = Rgb::new(0.0, 0.0, 0.0);
+        let white = Rgb::new(1.0, 1.0, 1.0);
+        let gray = black.interp(&white, 0.5);
+        assert!((gray.red - gray.green).abs() < 1e-3);
+        assert!(gray.red > 0.35 && gray.red < 0.5, "{:?}", gray);
+    }
+}
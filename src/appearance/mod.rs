@@ -1,3 +1,7 @@
+pub use self::color::{GetColor, SetColor};
 pub use self::opacity::{GetOpacity, Opacity, SetOpacity};
+pub use self::paint::{Gradient, GradientKind, Paint};
 
+pub mod color;
 pub mod opacity;
+pub mod paint;
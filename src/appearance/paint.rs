@@ -0,0 +1,307 @@
+use crate::animation::{lerp, Interpolate};
+use crate::consts::*;
+use crate::geom::Vector;
+use crate::path::Bounds;
+
+use nannou::color::Rgb;
+use nannou::lyon::math::{point, vector, Point};
+use nannou::lyon::path::Path;
+
+/// Fill or stroke of an object, either a single color or a gradient.
+///
+/// Gradients set on objects are laid out in a unit box covering the bounds
+/// of the object, with (0, 0) at the lower left. Before drawing they are
+/// placed onto the actual outline with `fit`, so backends receive gradients
+/// in object-local coordinates.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Paint {
+    Solid(Rgb),
+    Gradient(Gradient),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Gradient {
+    pub kind: GradientKind,
+    pub stops: Vec<(f32, Rgb)>, // Offsets within 0..=1, ascending
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GradientKind {
+    /// Colors change along the line from `start` to `end`
+    Linear { start: Point, end: Point },
+    /// Colors change from `center` outwards, up to `radius`
+    Radial { center: Point, radius: f32 },
+}
+
+impl Paint {
+    /// Colors evenly spread along `direction` across the object,
+    /// like manim's `set_color_by_gradient`
+    pub fn linear(colors: &[Rgb], direction: Vector) -> Self {
+        let length = direction.x.hypot(direction.y);
+        let half = if length > 0.0 {
+            vector(direction.x, direction.y) * (0.5 / length)
+        } else {
+            vector(0.5, 0.0)
+        };
+        let center = point(0.5, 0.5);
+        Self::gradient(
+            GradientKind::Linear {
+                start: center - half,
+                end: center + half,
+            },
+            colors,
+        )
+    }
+    /// Colors evenly spread from the center of the object outwards
+    pub fn radial(colors: &[Rgb]) -> Self {
+        Self::gradient(
+            GradientKind::Radial {
+                center: point(0.5, 0.5),
+                radius: 0.5,
+            },
+            colors,
+        )
+    }
+    fn gradient(kind: GradientKind, colors: &[Rgb]) -> Self {
+        match colors.len() {
+            0 => Paint::Solid(DEFAULT_FILL_COLOR),
+            1 => Paint::Solid(colors[0]),
+            n => {
+                let stops = colors
+                    .iter()
+                    .enumerate()
+                    .map(|(i, c)| (i as f32 / (n - 1) as f32, *c))
+                    .collect();
+                Paint::Gradient(Gradient { kind, stops })
+            }
+        }
+    }
+    /// Representative single color, used where gradients aren't supported
+    pub fn color(&self) -> Rgb {
+        match self {
+            Paint::Solid(color) => *color,
+            Paint::Gradient(gradient) => gradient.sample(0.5),
+        }
+    }
+    /// Color at `p`, for a paint placed with `fit`
+    pub fn at(&self, p: Point) -> Rgb {
+        match self {
+            Paint::Solid(color) => *color,
+            Paint::Gradient(gradient) => gradient.sample(gradient.offset(p)),
+        }
+    }
+    /// Place gradient from the unit box onto the bounds of `path`
+    pub fn fit(&self, path: &Path) -> Paint {
+        match self {
+            Paint::Solid(_) => self.clone(),
+            Paint::Gradient(gradient) => {
+                let bounds = Bounds::of_path(path);
+                if bounds.is_empty() {
+                    return self.clone();
+                }
+                let size = bounds.max - bounds.min;
+                let place = |p: Point| {
+                    point(
+                        lerp(bounds.min.x, bounds.max.x, p.x),
+                        lerp(bounds.min.y, bounds.max.y, p.y),
+                    )
+                };
+                let kind = match gradient.kind {
+                    GradientKind::Linear { start, end } => GradientKind::Linear {
+                        start: place(start),
+                        end: place(end),
+                    },
+                    GradientKind::Radial { center, radius } => GradientKind::Radial {
+                        center: place(center),
+                        radius: radius * size.x.max(size.y),
+                    },
+                };
+                Paint::Gradient(Gradient {
+                    kind,
+                    stops: gradient.stops.clone(),
+                })
+            }
+        }
+    }
+    // Gradient with same colors as this paint at every offset
+    fn to_gradient(&self, kind: GradientKind) -> Gradient {
+        match self {
+            Paint::Solid(color) => Gradient {
+                kind,
+                stops: vec![(0.0, *color), (1.0, *color)],
+            },
+            Paint::Gradient(gradient) => gradient.clone(),
+        }
+    }
+}
+
+impl Gradient {
+    /// Color at `offset` along the gradient, blended in OKLab between stops
+    pub fn sample(&self, offset: f32) -> Rgb {
+        let first = match self.stops.first() {
+            Some(stop) => stop,
+            None => return DEFAULT_FILL_COLOR,
+        };
+        if offset <= first.0 {
+            return first.1;
+        }
+        for pair in self.stops.windows(2) {
+            let (a, b) = (pair[0], pair[1]);
+            if offset <= b.0 {
+                let span = b.0 - a.0;
+                let t = if span > 0.0 {
+                    (offset - a.0) / span
+                } else {
+                    1.0
+                };
+                return a.1.interp(&b.1, t);
+            }
+        }
+        self.stops[self.stops.len() - 1].1
+    }
+    /// Offset along the gradient of point `p`, clamped to 0..=1
+    pub fn offset(&self, p: Point) -> f32 {
+        let offset = match self.kind {
+            GradientKind::Linear { start, end } => {
+                let axis = end - start;
+                let length = axis.square_length();
+                if length > 0.0 {
+                    (p - start).dot(axis) / length
+                } else {
+                    0.0
+                }
+            }
+            GradientKind::Radial { center, radius } => {
+                if radius > 0.0 {
+                    (p - center).length() / radius
+                } else {
+                    0.0
+                }
+            }
+        };
+        offset.max(0.0).min(1.0)
+    }
+}
+
+impl From<Rgb> for Paint {
+    fn from(color: Rgb) -> Self {
+        Paint::Solid(color)
+    }
+}
+
+impl From<Gradient> for Paint {
+    fn from(gradient: Gradient) -> Self {
+        Paint::Gradient(gradient)
+    }
+}
+
+impl Interpolate for GradientKind {
+    fn interp_mut(&mut self, other: &Self, progress: f32) {
+        *self = self.interp(other, progress);
+    }
+    // Linear and radial gradients can't blend, so they switch halfway
+    fn interp(&self, other: &Self, progress: f32) -> Self {
+        match (self, other) {
+            (
+                GradientKind::Linear { start, end },
+                GradientKind::Linear {
+                    start: start2,
+                    end: end2,
+                },
+            ) => GradientKind::Linear {
+                start: start.lerp(*start2, progress),
+                end: end.lerp(*end2, progress),
+            },
+            (
+                GradientKind::Radial { center, radius },
+                GradientKind::Radial {
+                    center: center2,
+                    radius: radius2,
+                },
+            ) => GradientKind::Radial {
+                center: center.lerp(*center2, progress),
+                radius: radius.interp(radius2, progress),
+            },
+            _ if progress < 0.5 => *self,
+            _ => *other,
+        }
+    }
+}
+
+impl Interpolate for Paint {
+    fn interp_mut(&mut self, other: &Self, progress: f32) {
+        *self = self.interp(other, progress);
+    }
+    fn interp(&self, other: &Self, progress: f32) -> Self {
+        if progress <= 0.0 {
+            return self.clone();
+        }
+        if progress >= 1.0 {
+            return other.clone();
+        }
+        let kind = match (self, other) {
+            (Paint::Solid(a), Paint::Solid(b)) => return Paint::Solid(a.interp(b, progress)),
+            (Paint::Gradient(a), Paint::Gradient(b)) => a.kind.interp(&b.kind, progress),
+            (Paint::Gradient(a), _) => a.kind,
+            (_, Paint::Gradient(b)) => b.kind,
+        };
+        let from = self.to_gradient(kind);
+        let to = other.to_gradient(kind);
+
+        // Both gradients are sampled at every stop of either one
+        let mut offsets: Vec<f32> = from
+            .stops
+            .iter()
+            .chain(to.stops.iter())
+            .map(|(offset, _)| *offset)
+            .collect();
+        offsets.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        offsets.dedup_by(|a, b| (*a - *b).abs() < 1e-6);
+
+        let stops = offsets
+            .into_iter()
+            .map(|offset| {
+                let color = from.sample(offset).interp(&to.sample(offset), progress);
+                (offset, color)
+            })
+            .collect();
+        Paint::Gradient(Gradient { kind, stops })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn linear_gradient_fits_bounds() {
+        let mut builder = Path::builder();
+        builder.move_to(point(-50.0, -10.0));
+        builder.line_to(point(50.0, -10.0));
+        builder.line_to(point(50.0, 10.0));
+        builder.line_to(point(-50.0, 10.0));
+        builder.close();
+        let path = builder.build();
+
+        let paint = Paint::linear(&[BLACK, WHITE], RIGHT).fit(&path);
+        assert_eq!(paint.at(point(-50.0, 0.0)), BLACK);
+        assert_eq!(paint.at(point(50.0, 0.0)), WHITE);
+        assert_eq!(paint.at(point(80.0, 5.0)), WHITE);
+    }
+
+    #[test]
+    fn interp_between_solid_and_gradient() {
+        let solid = Paint::Solid(BLACK);
+        let gradient = Paint::radial(&[WHITE, BLACK]);
+        assert_eq!(solid.interp(&gradient, 0.0), solid);
+        assert_eq!(solid.interp(&gradient, 1.0), gradient);
+
+        match solid.interp(&gradient, 0.5) {
+            Paint::Gradient(mid) => {
+                assert_eq!(mid.sample(1.0), BLACK);
+                assert!(mid.sample(0.0).red > 0.0);
+            }
+            paint => panic!("expected gradient, got {:?}", paint),
+        }
+    }
+}
@@ -0,0 +1,72 @@
+use crate::animation::{Action, Interpolate, TargetAction};
+use crate::arena;
+use crate::arena::{Id, Index};
+use crate::object::Object;
+use crate::scene::Resource;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ArcId(pub Index);
+
+impl ArcId {
+    /// Change angle spanned by the arc, in degrees
+    pub fn set_angle(&self, to: f32) -> TargetAction {
+        let id: Index = Self::into(*self);
+        TargetAction::new(
+            Id(id),
+            Action::ArcAction(ArcAction::SetAngle { from: to, to }),
+        )
+    }
+    pub fn set_start_angle(&self, to: f32) -> TargetAction {
+        let id: Index = Self::into(*self);
+        TargetAction::new(
+            Id(id),
+            Action::ArcAction(ArcAction::SetStartAngle { from: to, to }),
+        )
+    }
+}
+
+// Actionable is auto-implemented on `Into<Index>`
+impl From<Index> for ArcId {
+    fn from(index: Index) -> Self {
+        Self(index)
+    }
+}
+
+impl From<ArcId> for Index {
+    fn from(id: ArcId) -> Self {
+        id.0
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ArcAction {
+    SetAngle { from: f32, to: f32 },
+    SetStartAngle { from: f32, to: f32 },
+}
+
+impl ArcAction {
+    pub fn init(&mut self, object: &mut arena::Object, _resource: &Resource) {
+        if let Object::Arc(ref arc) = object.inner {
+            match self {
+                ArcAction::SetAngle { ref mut from, .. } => {
+                    *from = arc.angle();
+                }
+                ArcAction::SetStartAngle { ref mut from, .. } => {
+                    *from = arc.start_angle();
+                }
+            }
+        }
+    }
+    pub fn update(&mut self, object: &mut arena::Object, progress: f32) {
+        if let Object::Arc(ref mut arc) = object.inner {
+            match self {
+                ArcAction::SetAngle { from, to } => {
+                    arc.set_angle(from.interp(to, progress));
+                }
+                ArcAction::SetStartAngle { from, to } => {
+                    arc.set_start_angle(from.interp(to, progress));
+                }
+            }
+        }
+    }
+}
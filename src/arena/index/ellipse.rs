@@ -0,0 +1,17 @@
+use crate::arena::Index;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EllipseId(pub Index);
+
+// Actionable is auto-implemented on `Into<Index>`
+impl From<Index> for EllipseId {
+    fn from(index: Index) -> Self {
+        Self(index)
+    }
+}
+
+impl From<EllipseId> for Index {
+    fn from(id: EllipseId) -> Self {
+        id.0
+    }
+}
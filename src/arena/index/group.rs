@@ -0,0 +1,43 @@
+use crate::animation::{Action, ChangeSize, TargetAction};
+use crate::arena::{HasArena, Id, Index};
+use crate::geom::dimension;
+use crate::scene::Scene;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GroupId(pub Index);
+
+impl GroupId {
+    /// Make `child` part of the group at this point of the timeline,
+    /// keeping where it is in the scene. Group is re-centered on its children.
+    pub fn add<T>(&self, scene: &mut Scene, child: T) -> Self
+    where
+        T: Into<Index> + Copy,
+    {
+        scene.add_child(&Id(self.0), &Id(child.into()));
+        *self
+    }
+    pub fn scale_by(&self, by: f32) -> TargetAction {
+        let id: Index = Self::into(*self);
+        TargetAction::new(
+            Id(id),
+            Action::ChangeSize(ChangeSize::ScaleDimension {
+                from: dimension(1.0, 1.0), // This is dummy, overwritten in Action::init()
+                to: dimension(1.0, 1.0),
+                by,
+            }),
+        )
+    }
+}
+
+// Actionable is auto-implemented on `Into<Index>`
+impl From<Index> for GroupId {
+    fn from(index: Index) -> Self {
+        Self(index)
+    }
+}
+
+impl From<GroupId> for Index {
+    fn from(id: GroupId) -> Self {
+        id.0
+    }
+}
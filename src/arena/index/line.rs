@@ -0,0 +1,83 @@
+use crate::animation::{Action, Interpolate, TargetAction};
+use crate::arena;
+use crate::arena::{Id, Index};
+use crate::geom::{point, Point};
+use crate::object::{ArrowTip, Object};
+use crate::scene::Resource;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LineId(pub Index);
+
+impl LineId {
+    /// Move ends of the line to `start` and `end`
+    pub fn put_start_and_end_on(&self, start: Point, end: Point) -> TargetAction {
+        let id: Index = Self::into(*self);
+        TargetAction::new(
+            Id(id),
+            Action::LineAction(LineAction::PutStartAndEnd {
+                from: (point(), point()), // This is dummy, overwritten in Action::init()
+                to: (start, end),
+            }),
+        )
+    }
+    /// Put tips on the ends of the line, `None` for a plain end
+    pub fn set_tips(&self, start: Option<ArrowTip>, end: Option<ArrowTip>) -> TargetAction {
+        let id: Index = Self::into(*self);
+        TargetAction::new(
+            Id(id),
+            Action::LineAction(LineAction::SetTips { start, end }),
+        )
+    }
+}
+
+// Actionable is auto-implemented on `Into<Index>`
+impl From<Index> for LineId {
+    fn from(index: Index) -> Self {
+        Self(index)
+    }
+}
+
+impl From<LineId> for Index {
+    fn from(id: LineId) -> Self {
+        id.0
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum LineAction {
+    PutStartAndEnd {
+        from: (Point, Point),
+        to: (Point, Point),
+    },
+    SetTips {
+        start: Option<ArrowTip>,
+        end: Option<ArrowTip>,
+    },
+}
+
+impl LineAction {
+    pub fn init(&mut self, object: &mut arena::Object, _resource: &Resource) {
+        if let Object::Line(ref line) = object.inner {
+            match self {
+                LineAction::PutStartAndEnd { ref mut from, .. } => {
+                    *from = line.start_and_end();
+                }
+                LineAction::SetTips { .. } => (),
+            }
+        }
+    }
+    pub fn update(&mut self, object: &mut arena::Object, progress: f32) {
+        if let Object::Line(ref mut line) = object.inner {
+            match self {
+                LineAction::PutStartAndEnd { from, to } => {
+                    let start = from.0.interp(&to.0, progress);
+                    let end = from.1.interp(&to.1, progress);
+                    line.set_start_and_end(start, end);
+                }
+                LineAction::SetTips { start, end } => {
+                    line.set_tips(*start, *end);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,92 @@
+use crate::animation::{Action, Interpolate, TargetAction};
+use crate::appearance::Paint;
+use crate::arena;
+use crate::arena::{Id, Index};
+use crate::object::Object;
+use crate::scene::Resource;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MathTexId(pub Index);
+
+impl MathTexId {
+    /// Fill part `index` of the formula apart from the rest
+    pub fn set_part_color<P: Into<Paint>>(&self, index: usize, to: P) -> TargetAction {
+        self.color(None, vec![index], to.into())
+    }
+    /// Fill every part containing `pattern`, like manim's `set_color_by_tex`
+    pub fn set_color_by_tex<P: Into<Paint>>(&self, pattern: &str, to: P) -> TargetAction {
+        self.color(Some(String::from(pattern)), Vec::new(), to.into())
+    }
+    fn color(&self, pattern: Option<String>, parts: Vec<usize>, to: Paint) -> TargetAction {
+        let id: Index = Self::into(*self);
+        TargetAction::new(
+            Id(id),
+            Action::MathTexAction(MathTexAction::ColorParts {
+                pattern,
+                parts,
+                glyphs: Vec::new(), // Resolved from parts on init
+                from: Vec::new(),
+                to,
+            }),
+        )
+    }
+}
+
+// Actionable is auto-implemented on `Into<Index>`
+impl From<Index> for MathTexId {
+    fn from(index: Index) -> Self {
+        Self(index)
+    }
+}
+
+impl From<MathTexId> for Index {
+    fn from(id: MathTexId) -> Self {
+        id.0
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum MathTexAction {
+    ColorParts {
+        pattern: Option<String>,
+        parts: Vec<usize>,
+        glyphs: Vec<usize>,
+        from: Vec<Paint>,
+        to: Paint,
+    },
+}
+
+impl MathTexAction {
+    pub fn init(&mut self, object: &mut arena::Object, _resource: &Resource) {
+        if let Object::MathTex(ref tex) = object.inner {
+            match self {
+                MathTexAction::ColorParts {
+                    pattern,
+                    ref mut parts,
+                    ref mut glyphs,
+                    ref mut from,
+                    ..
+                } => {
+                    if let Some(pattern) = pattern {
+                        *parts = tex.part_indices(pattern);
+                    }
+                    *glyphs = parts.iter().flat_map(|i| tex.part_glyphs(*i)).collect();
+                    *from = glyphs.iter().map(|i| tex.glyph_color(*i)).collect();
+                }
+            }
+        }
+    }
+    pub fn update(&mut self, object: &mut arena::Object, progress: f32) {
+        if let Object::MathTex(ref mut tex) = object.inner {
+            match self {
+                MathTexAction::ColorParts {
+                    glyphs, from, to, ..
+                } => {
+                    for (i, from) in glyphs.iter().zip(from.iter()) {
+                        tex.set_glyph_color(*i, from.interp(to, progress));
+                    }
+                }
+            }
+        }
+    }
+}
@@ -1,7 +1,23 @@
+mod arc;
 mod circle;
+mod ellipse;
+mod group;
+mod line;
+mod math_tex;
+mod polygon;
 mod rectangle;
 mod text;
+mod value_tracker;
+mod vpath;
 
+pub use arc::{ArcAction, ArcId};
 pub use circle::{CircleAction, CircleId};
+pub use ellipse::EllipseId;
+pub use group::GroupId;
+pub use line::{LineAction, LineId};
+pub use math_tex::{MathTexAction, MathTexId};
+pub use polygon::PolygonId;
 pub use rectangle::{RectangleAction, RectangleId};
-pub use text::{TextAction, TextId};
+pub use text::{SplitText, StyleChange, TextAction, TextId};
+pub use value_tracker::{ValueTrackerAction, ValueTrackerId};
+pub use vpath::VPathId;
@@ -0,0 +1,17 @@
+use crate::arena::Index;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PolygonId(pub Index);
+
+// Actionable is auto-implemented on `Into<Index>`
+impl From<Index> for PolygonId {
+    fn from(index: Index) -> Self {
+        Self(index)
+    }
+}
+
+impl From<PolygonId> for Index {
+    fn from(id: PolygonId) -> Self {
+        id.0
+    }
+}
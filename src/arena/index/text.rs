@@ -1,9 +1,13 @@
 // use crate::animation::Interpolate;
-use crate::animation::{Action, Interpolate, TargetAction};
+use crate::animation::{Action, Interpolate, RunCommand, TargetAction};
+use crate::appearance::{GetColor, GetOpacity, Paint, SetColor, SetOpacity};
 use crate::arena;
-use crate::arena::{Id, Index};
-use crate::object::Object;
-use crate::scene::Resource;
+use crate::arena::{AddObject, Arena, GroupId, HasArena, Id, Index, NodeArena, VPathId};
+use crate::geom::{GetPosition, SetOrientation, SetPosition};
+use crate::import::{FontFamily, Slant, Weight};
+use crate::object::text::{Alignment, TextStyle};
+use crate::object::{Object, VPathBuilder};
+use crate::scene::{Resource, Scene};
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct TextId(pub Index);
@@ -23,6 +27,148 @@ impl TextId {
             Action::TextAction(TextAction::SetSize { from: 1, to }),
         )
     }
+    /// Typeface to draw with, in the current weight and slant
+    pub fn set_font(&self, font: FontFamily) -> TargetAction {
+        self.set_style(StyleChange::Font(font))
+    }
+    pub fn set_weight(&self, weight: Weight) -> TargetAction {
+        self.set_style(StyleChange::Weight(weight))
+    }
+    pub fn set_slant(&self, slant: Slant) -> TargetAction {
+        self.set_style(StyleChange::Slant(slant))
+    }
+    pub fn set_alignment(&self, alignment: Alignment) -> TargetAction {
+        self.set_style(StyleChange::Alignment(alignment))
+    }
+    /// Distance between baselines, relative to the font size
+    pub fn set_line_spacing(&self, spacing: f32) -> TargetAction {
+        self.set_style(StyleChange::LineSpacing(spacing))
+    }
+    /// Wrap lines between words to fit `width`, or never with `None`
+    pub fn set_max_width(&self, width: Option<f32>) -> TargetAction {
+        self.set_style(StyleChange::MaxWidth(width))
+    }
+    fn set_style(&self, change: StyleChange) -> TargetAction {
+        let id: Index = Self::into(*self);
+        TargetAction::new(
+            Id(id),
+            Action::TextAction(TextAction::SetStyle {
+                change,
+                from: TextStyle::default(), // Overwritten on init
+                to: TextStyle::default(),
+            }),
+        )
+    }
+    /// Replace the text by a group of its words, each a group of glyphs, so
+    /// that every word and glyph can be colored, moved or faded on its own.
+    /// Glyphs keep the colors of the text, and the group its placement.
+    /// Like other commands, the text is split at this point of the timeline,
+    /// as left by the commands before.
+    pub fn split(&self, scene: &mut Scene) -> SplitText {
+        let id = Id(self.0);
+        let (glyph_count, word_count) = match scene.get(&id) {
+            Some(arena::Object {
+                inner: Object::Text(text),
+                ..
+            }) => (text.glyph_paths().len(), text.word_glyphs().len()),
+            _ => (0, 0),
+        };
+        // Objects are made now, so they can be animated, and filled in by
+        // the command
+        let split = SplitText {
+            text: scene.group(),
+            words: (0..word_count).map(|_| scene.group()).collect(),
+            glyphs: (0..glyph_count)
+                .map(|_| scene.path(VPathBuilder::new()))
+                .collect(),
+        };
+        scene.commands.split(id, split.clone());
+        split
+    }
+    /// Fill glyph `index` apart from the rest, counting characters other
+    /// than whitespace
+    pub fn set_glyph_color<P: Into<Paint>>(&self, index: usize, to: P) -> TargetAction {
+        let id: Index = Self::into(*self);
+        TargetAction::new(
+            Id(id),
+            Action::TextAction(TextAction::ColorGlyphs {
+                pattern: None,
+                glyphs: vec![index],
+                from: Vec::new(),
+                to: to.into(),
+            }),
+        )
+    }
+    /// Fill every occurrence of `pattern`, like manim's `t2c`
+    pub fn set_color_by_substring<P: Into<Paint>>(&self, pattern: &str, to: P) -> TargetAction {
+        let id: Index = Self::into(*self);
+        TargetAction::new(
+            Id(id),
+            Action::TextAction(TextAction::ColorGlyphs {
+                pattern: Some(String::from(pattern)),
+                glyphs: Vec::new(), // Resolved from text on init
+                from: Vec::new(),
+                to: to.into(),
+            }),
+        )
+    }
+}
+
+/// Objects of a text split by `TextId::split`
+#[derive(Debug, Clone, PartialEq)]
+pub struct SplitText {
+    pub text: GroupId,
+    pub words: Vec<GroupId>,
+    pub glyphs: Vec<VPathId>, // Counted like glyphs of `Text`
+}
+
+impl SplitText {
+    // Glyphs of text `id` as it is now, put in their words, and the group in
+    // place of the text, which is removed
+    pub(crate) fn place(&self, id: Id, objects: &mut Arena<arena::Object>) {
+        let (text, parent) = match objects.get(id.0) {
+            Some(arena::Object {
+                inner: Object::Text(text),
+                parent,
+                ..
+            }) => (text.clone(), *parent),
+            _ => return,
+        };
+        let world = objects.world_transform(id);
+
+        let outlines = text.glyph_paths();
+        for (i, (glyph, outline)) in self.glyphs.iter().zip(outlines.iter()).enumerate() {
+            let mut path = VPathBuilder::from_path(outline).build();
+            path.set_fill_paint(text.glyph_color(i));
+            path.set_stroke_paint(text.stroke_paint());
+            path.set_stroke_weight(text.stroke_weight());
+            path.show();
+            if let Some(node) = objects.get_mut(glyph.0) {
+                node.inner = path.into();
+            }
+        }
+        for (word, range) in self.words.iter().zip(text.word_glyphs()) {
+            for glyph in self.glyphs.iter().take(range.end).skip(range.start) {
+                objects.attach(Id(word.0), Id(glyph.0));
+            }
+            objects.attach(Id(self.text.0), Id(word.0));
+        }
+
+        // Group takes the place of the text, whose glyphs are laid out around it
+        if let Some(node) = objects.get_mut(self.text.0) {
+            let center = world.apply(node.position().into());
+            node.move_to(center.x, center.y);
+            node.rotate_to(world.orientation);
+            if let Object::Group(ref mut inner) = node.inner {
+                inner.set_scale(world.scale);
+            }
+            node.set_opacity(if text.is_visible() { text.alpha() } else { 0.0 });
+        }
+        if let Some(parent) = parent {
+            objects.attach(Id(parent), Id(self.text.0));
+        }
+        objects.delete(id);
+    }
 }
 
 // Actionable is auto-implemented on `Into<Index>`
@@ -40,8 +186,50 @@ impl From<TextId> for Index {
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum TextAction {
-    SetSize { from: u32, to: u32 },
-    ScaleSize { from: u32, to: u32, by: f32 },
+    SetSize {
+        from: u32,
+        to: u32,
+    },
+    ScaleSize {
+        from: u32,
+        to: u32,
+        by: f32,
+    },
+    ColorGlyphs {
+        pattern: Option<String>,
+        glyphs: Vec<usize>,
+        from: Vec<Paint>,
+        to: Paint,
+    },
+    SetStyle {
+        change: StyleChange,
+        from: TextStyle,
+        to: TextStyle,
+    },
+}
+
+/// Part of `TextStyle` changed by `TextAction::SetStyle`
+#[derive(Debug, Clone, PartialEq)]
+pub enum StyleChange {
+    Font(FontFamily),
+    Weight(Weight),
+    Slant(Slant),
+    Alignment(Alignment),
+    LineSpacing(f32),
+    MaxWidth(Option<f32>),
+}
+
+impl StyleChange {
+    fn apply(&self, style: &mut TextStyle) {
+        match self {
+            StyleChange::Font(font) => style.font = font.clone(),
+            StyleChange::Weight(weight) => style.weight = *weight,
+            StyleChange::Slant(slant) => style.slant = *slant,
+            StyleChange::Alignment(alignment) => style.alignment = *alignment,
+            StyleChange::LineSpacing(spacing) => style.line_spacing = *spacing,
+            StyleChange::MaxWidth(width) => style.max_width = *width,
+        }
+    }
 }
 
 impl TextAction {
@@ -59,6 +247,26 @@ impl TextAction {
                     *from = text.font_size();
                     *to = (text.font_size() as f32 * (*by)) as u32;
                 }
+                TextAction::ColorGlyphs {
+                    pattern,
+                    ref mut glyphs,
+                    ref mut from,
+                    ..
+                } => {
+                    if let Some(pattern) = pattern {
+                        *glyphs = text.glyph_indices(pattern);
+                    }
+                    *from = glyphs.iter().map(|i| text.glyph_color(*i)).collect();
+                }
+                TextAction::SetStyle {
+                    change,
+                    ref mut from,
+                    ref mut to,
+                } => {
+                    *from = text.style().clone();
+                    *to = from.clone();
+                    change.apply(to);
+                }
             }
         }
     }
@@ -73,6 +281,16 @@ impl TextAction {
                     let now = from.interp(to, progress);
                     text.set_font_size(now);
                 }
+                TextAction::ColorGlyphs {
+                    glyphs, from, to, ..
+                } => {
+                    for (i, from) in glyphs.iter().zip(from.iter()) {
+                        text.set_glyph_color(*i, from.interp(to, progress));
+                    }
+                }
+                TextAction::SetStyle { from, to, .. } => {
+                    text.set_style(from.interp(to, progress));
+                }
             }
         }
     }
@@ -0,0 +1,90 @@
+use crate::animation::{Action, Actionable, Interpolate, TargetAction};
+use crate::arena;
+use crate::arena::{HasArena, Id, Index};
+use crate::geom::{point, GetPosition, Point};
+use crate::object::Object;
+use crate::scene::Resource;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ValueTrackerId(pub Index);
+
+impl ValueTrackerId {
+    /// Animate the tracked value to `to`
+    pub fn set_value(&self, to: f32) -> TargetAction {
+        let id: Index = Self::into(*self);
+        TargetAction::new(
+            Id(id),
+            Action::ValueTrackerAction(ValueTrackerAction::SetValue { from: to, to }),
+        )
+    }
+    pub fn increment_value(&self, by: f32) -> TargetAction {
+        let id: Index = Self::into(*self);
+        TargetAction::new(
+            Id(id),
+            Action::ValueTrackerAction(ValueTrackerAction::IncrementValue { from: 0.0, by }),
+        )
+    }
+    /// Animate the tracked point to `to`, same as moving the tracker
+    pub fn set_point(&self, to: Point) -> TargetAction {
+        self.move_to(to)
+    }
+    /// Current value, for updaters reading the tracker
+    pub fn get_value<A: HasArena>(&self, arena: &A) -> f32 {
+        match arena.get(&Id(self.0)).map(|object| &object.inner) {
+            Some(Object::ValueTracker(tracker)) => tracker.value(),
+            _ => 0.0,
+        }
+    }
+    pub fn get_point<A: HasArena>(&self, arena: &A) -> Point {
+        arena
+            .get(&Id(self.0))
+            .map(|object| object.position())
+            .unwrap_or_else(point)
+    }
+}
+
+// Actionable is auto-implemented on `Into<Index>`
+impl From<Index> for ValueTrackerId {
+    fn from(index: Index) -> Self {
+        Self(index)
+    }
+}
+
+impl From<ValueTrackerId> for Index {
+    fn from(id: ValueTrackerId) -> Self {
+        id.0
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ValueTrackerAction {
+    SetValue { from: f32, to: f32 },
+    IncrementValue { from: f32, by: f32 },
+}
+
+impl ValueTrackerAction {
+    pub fn init(&mut self, object: &mut arena::Object, _resource: &Resource) {
+        if let Object::ValueTracker(ref tracker) = object.inner {
+            match self {
+                ValueTrackerAction::SetValue { ref mut from, .. } => {
+                    *from = tracker.value();
+                }
+                ValueTrackerAction::IncrementValue { ref mut from, .. } => {
+                    *from = tracker.value();
+                }
+            }
+        }
+    }
+    pub fn update(&mut self, object: &mut arena::Object, progress: f32) {
+        if let Object::ValueTracker(ref mut tracker) = object.inner {
+            match self {
+                ValueTrackerAction::SetValue { from, to } => {
+                    tracker.set_value(from.interp(to, progress));
+                }
+                ValueTrackerAction::IncrementValue { from, by } => {
+                    tracker.set_value(from.interp(&(*from + *by), progress));
+                }
+            }
+        }
+    }
+}
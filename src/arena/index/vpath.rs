@@ -0,0 +1,17 @@
+use crate::arena::Index;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct VPathId(pub Index);
+
+// Actionable is auto-implemented on `Into<Index>`
+impl From<Index> for VPathId {
+    fn from(index: Index) -> Self {
+        Self(index)
+    }
+}
+
+impl From<VPathId> for Index {
+    fn from(id: VPathId) -> Self {
+        id.0
+    }
+}
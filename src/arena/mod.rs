@@ -2,10 +2,18 @@ pub use generational_arena::{Arena, Index};
 
 pub use tree::{Id, NodeArena, NodeIndex, Object};
 
-pub use index::{CircleAction, CircleId, RectangleAction, RectangleId, TextAction, TextId};
+pub use index::{ArcAction, ArcId, CircleAction, CircleId, EllipseId, GroupId};
+pub use index::{LineAction, LineId, MathTexAction, MathTexId, PolygonId};
+pub use index::{RectangleAction, RectangleId};
+pub use index::{SplitText, StyleChange, TextAction, TextId, VPathId};
+pub use index::{ValueTrackerAction, ValueTrackerId};
 
 pub use property::rotate::Rotate;
 
+use crate::geom::Point;
+use crate::import::{SvgError, TexError};
+use crate::object::VPathBuilder;
+
 mod index;
 mod property;
 mod tree;
@@ -15,10 +23,40 @@ pub trait HasArena {
     fn get_mut(&mut self, index: &Id) -> Option<&mut Object>;
     fn get(&self, index: &Id) -> Option<&Object>;
     fn get_parent(&self, index: &Id) -> Option<&Object>;
+    fn add_child(&mut self, parent: &Id, child: &Id);
 }
 
 pub trait AddObject {
     fn circle(&mut self) -> CircleId;
     fn rectangle(&mut self) -> RectangleId;
     fn text(&mut self, text: &str) -> TextId;
+    /// Math typeset by LaTeX, or why it couldn't be
+    fn try_math_tex(&mut self, parts: &[&str]) -> Result<MathTexId, TexError>;
+    /// Like `try_math_tex`, panicking if typesetting fails. Scenes are code,
+    /// so failing to typeset is treated like a bad argument.
+    fn math_tex(&mut self, parts: &[&str]) -> MathTexId {
+        self.try_math_tex(parts)
+            .unwrap_or_else(|e| panic!("Failed to typeset {:?}: {}", parts, e))
+    }
+    fn dot(&mut self) -> CircleId;
+    fn line(&mut self, start: Point, end: Point) -> LineId;
+    fn arrow(&mut self, start: Point, end: Point) -> LineId;
+    fn polygon(&mut self, vertices: &[Point]) -> PolygonId;
+    fn regular_polygon(&mut self, sides: usize) -> PolygonId;
+    fn arc(&mut self, angle: f32) -> ArcId;
+    fn sector(&mut self, angle: f32) -> ArcId;
+    fn annulus(&mut self, inner_ratio: f32) -> ArcId;
+    fn ellipse(&mut self) -> EllipseId;
+    fn path(&mut self, builder: VPathBuilder) -> VPathId;
+    fn group(&mut self) -> GroupId;
+    /// Drawing of an SVG file, as a group of paths, or why it couldn't be read
+    fn try_svg(&mut self, file: &str) -> Result<GroupId, SvgError>;
+    /// Like `try_svg`, panicking if the file can't be read. Like `math_tex`,
+    /// a drawing that can't be read is a bad argument.
+    fn svg(&mut self, file: &str) -> GroupId {
+        self.try_svg(file)
+            .unwrap_or_else(|e| panic!("Failed to import {:?}: {}", file, e))
+    }
+    /// Invisible number to animate and read from updaters
+    fn value_tracker(&mut self, value: f32) -> ValueTrackerId;
 }
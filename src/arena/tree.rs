@@ -1,13 +1,15 @@
 use super::{Arena, HasArena};
 use crate::animation::PathCompletion;
-use crate::appearance::{GetOpacity, Opacity, SetOpacity};
-use crate::draw::Draw;
-use crate::geom::{Dimension, GetDimension, SetDimension};
+use crate::appearance::{GetColor, GetOpacity, Opacity, Paint, SetColor, SetOpacity};
+use crate::draw::{Backend, Draw, Transform};
+use crate::geom::{dimension, point_at, Dimension, GetDimension, SetDimension};
 use crate::geom::{GetOrientation, GetPosition, Point, SetOrientation, SetPosition};
+use crate::morph::Shape;
 use crate::object::Object as InnerObject;
 use crate::scene::Scene;
 
 use generational_arena::Index;
+use nannou::color::Rgb;
 
 // pub type Object = Node; // Treat Node like an object
 // pub type Id = NodeIndex; // Short-hand for readability
@@ -15,27 +17,41 @@ use generational_arena::Index;
 pub use Node as Object; // Treat Node like an object
 pub use NodeIndex as Id; // Short-hand for readability
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Node {
     pub parent: Option<Index>,
-    pub child: Option<Index>,
+    pub children: Vec<Index>,
     pub inner: InnerObject,
+    pub shape: Option<Shape>, // Drawn instead of `inner` while morphing
 }
 
 impl Node {
     pub fn new(object: InnerObject) -> Self {
         Self {
             parent: None,
-            child: None,
+            children: Vec::new(),
             inner: object,
+            shape: None,
         }
     }
-    pub fn set_child(&mut self, idx: Index) {
-        self.child = Some(idx);
+    pub fn add_child(&mut self, idx: Index) {
+        if !self.children.contains(&idx) {
+            self.children.push(idx);
+        }
+    }
+    pub fn remove_child(&mut self, idx: Index) {
+        self.children.retain(|child| *child != idx);
     }
     pub fn set_parent(&mut self, idx: Index) {
         self.parent = Some(idx);
     }
+    /// Placement relative to parent, which also applies to children
+    pub fn transform(&self) -> Transform {
+        self.inner.transform()
+    }
+    pub fn is_group(&self) -> bool {
+        matches!(self.inner, InnerObject::Group(_))
+    }
 }
 
 impl SetPosition for Node {
@@ -84,8 +100,45 @@ impl SetDimension for Node {
 }
 
 impl Draw for Node {
-    fn draw(&self, draw: nannou::Draw) {
-        Draw::draw(&self.inner, draw);
+    fn draw<B: Backend>(&self, backend: &mut B) {
+        match self.shape {
+            Some(ref shape) if self.is_visible() => {
+                let transform = Transform::new(self.position(), self.orientation());
+                shape.draw(backend, &transform, self.alpha());
+            }
+            Some(_) => (),
+            None => Draw::draw(&self.inner, backend),
+        }
+    }
+}
+
+impl GetColor for Node {
+    fn color(&self) -> Rgb {
+        GetColor::color(&self.inner)
+    }
+    fn stroke_color(&self) -> Rgb {
+        GetColor::stroke_color(&self.inner)
+    }
+    fn stroke_weight(&self) -> f32 {
+        GetColor::stroke_weight(&self.inner)
+    }
+    fn fill_paint(&self) -> Paint {
+        GetColor::fill_paint(&self.inner)
+    }
+    fn stroke_paint(&self) -> Paint {
+        GetColor::stroke_paint(&self.inner)
+    }
+}
+
+impl SetColor for Node {
+    fn set_fill_paint(&mut self, paint: Paint) {
+        SetColor::set_fill_paint(&mut self.inner, paint)
+    }
+    fn set_stroke_paint(&mut self, paint: Paint) {
+        SetColor::set_stroke_paint(&mut self.inner, paint)
+    }
+    fn set_stroke_weight(&mut self, weight: f32) {
+        SetColor::set_stroke_weight(&mut self.inner, weight)
     }
 }
 
@@ -116,14 +169,108 @@ impl PathCompletion for Node {
 pub trait NodeArena {
     fn add(&mut self, node: Node) -> NodeIndex;
     fn delete(&mut self, id: NodeIndex);
+    fn attach(&mut self, parent: NodeIndex, child: NodeIndex);
+    fn world_transform(&self, id: NodeIndex) -> Transform;
+    fn descendants(&self, id: NodeIndex) -> Vec<NodeIndex>;
 }
 
 impl NodeArena for Arena<Node> {
     fn add(&mut self, node: Node) -> NodeIndex {
         NodeIndex(self.insert(node))
     }
+    // Removes descendants too
     fn delete(&mut self, id: NodeIndex) {
-        self.remove(id.0);
+        if let Some(node) = self.remove(id.0) {
+            if let Some(parent) = node.parent.and_then(|parent| self.get_mut(parent)) {
+                parent.remove_child(id.0);
+            }
+            for child in node.children {
+                if let Some(child_node) = self.get_mut(child) {
+                    child_node.parent = None;
+                }
+                self.delete(NodeIndex(child));
+            }
+        }
+    }
+    /// Make `child` part of `parent`, keeping where it is in the scene
+    fn attach(&mut self, parent: NodeIndex, child: NodeIndex) {
+        if parent == child || self.get(parent.0).is_none() || self.get(child.0).is_none() {
+            return;
+        }
+        // Express child in the space of its new parent
+        let local = self
+            .world_transform(child)
+            .then(&self.world_transform(parent).inverse());
+        let old_parent = self[child.0].parent;
+        if let Some(old) = old_parent.and_then(|old| self.get_mut(old)) {
+            old.remove_child(child.0);
+        }
+        let node = &mut self[child.0];
+        node.parent = Some(parent.0);
+        node.move_to(local.position.x, local.position.y);
+        node.rotate_to(local.orientation);
+        let size = dimension(node.width() * local.scale, node.height() * local.scale);
+        node.set_size(size);
+        self[parent.0].add_child(child.0);
+        fit_group(self, parent);
+    }
+    /// Objects inside `id` at any depth, parents before their children
+    fn descendants(&self, id: NodeIndex) -> Vec<NodeIndex> {
+        let mut found = Vec::new();
+        if let Some(node) = self.get(id.0) {
+            for child in node.children.iter() {
+                found.push(NodeIndex(*child));
+                found.extend(self.descendants(NodeIndex(*child)));
+            }
+        }
+        found
+    }
+    /// Placement in the scene, composed of all ancestors
+    fn world_transform(&self, id: NodeIndex) -> Transform {
+        match self.get(id.0) {
+            Some(node) => {
+                let local = node.transform();
+                match node.parent {
+                    Some(parent) => local.then(&self.world_transform(NodeIndex(parent))),
+                    None => local,
+                }
+            }
+            None => Transform::identity(),
+        }
+    }
+}
+
+// Center group on its children, and update its size at scale 1
+fn fit_group(arena: &mut Arena<Node>, id: NodeIndex) {
+    let children = match arena.get(id.0) {
+        Some(Node {
+            inner: InnerObject::Group(_),
+            children,
+            ..
+        }) => children.clone(),
+        _ => return,
+    };
+    let mut min = point_at(std::f32::MAX, std::f32::MAX);
+    let mut max = point_at(std::f32::MIN, std::f32::MIN);
+    for child in children.iter().filter_map(|child| arena.get(*child)) {
+        let p = child.position();
+        let (w, h) = (child.width() / 2.0, child.height() / 2.0);
+        min = point_at(min.x.min(p.x - w), min.y.min(p.y - h));
+        max = point_at(max.x.max(p.x + w), max.y.max(p.y + h));
+    }
+    if children.is_empty() {
+        return;
+    }
+    let center = (min + max) * 0.5;
+    for child in children.iter() {
+        if let Some(child) = arena.get_mut(*child) {
+            child.move_by(-center.x, -center.y);
+        }
+    }
+    if let InnerObject::Group(ref mut group) = arena[id.0].inner {
+        let offset = group.transform().apply(center.into());
+        group.move_to(offset.x, offset.y);
+        group.set_base(dimension(max.x - min.x, max.y - min.y));
     }
 }
 
@@ -134,11 +281,11 @@ impl NodeIndex {
     pub fn new(idx: Index) -> Self {
         Self(idx)
     }
-    pub fn set_child(&self, scene: &mut Scene, child: &NodeIndex) {
-        scene.get_mut(&self).map(|obj| obj.set_child(child.0));
+    pub fn add_child(&self, scene: &mut Scene, child: &NodeIndex) {
+        scene.add_child(self, child);
     }
     pub fn set_parent(&self, scene: &mut Scene, parent: &NodeIndex) {
-        scene.get_mut(&self).map(|obj| obj.set_parent(parent.0));
+        scene.add_child(parent, self);
     }
 }
 
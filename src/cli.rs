@@ -0,0 +1,279 @@
+//! Command-line options, modeled after the manim CLI.
+//!
+//! ```text
+//! manim_rs [preview|render|list] [--scene NAME] [--from SEC] [--to SEC]
+//!          [--quality l|m|h|k] [--resolution WxH] [--fps FPS]
+//!          [--format png|video|svg] [--out PATH]
+//! ```
+use crate::render::RenderSettings;
+
+use std::path::{Path, PathBuf};
+
+pub const USAGE: &str = "\
+Usage: manim_rs [preview|render|list] [options]
+
+Commands:
+    preview              Open a window and play the scene (default)
+    render               Render the scene without a window
+    list                 Print names of available scenes
+
+Options:
+    -s, --scene NAME     Scene to play or render (default first scene)
+        --from SEC       Start time in seconds (default 0)
+        --to SEC         End time in seconds (default end of scene)
+    -q, --quality Q      l (480p15), m (720p30), h (1080p60) or k (2160p60)
+    -r, --resolution WxH Override resolution of the quality preset
+        --fps FPS        Override frame rate of the quality preset
+    -f, --format F       png, video or svg (default guessed from --out)
+    -o, --out PATH       Output directory or file (default out/)
+    -h, --help           Print this message";
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Mode {
+    Preview,
+    Render,
+    List,
+}
+
+/// Resolution and frame rate presets, same as manim's `-ql/-qm/-qh/-qk`
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Quality {
+    Low,
+    Medium,
+    High,
+    Production,
+}
+
+impl Quality {
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "l" | "low" => Some(Quality::Low),
+            "m" | "medium" => Some(Quality::Medium),
+            "h" | "high" => Some(Quality::High),
+            "k" | "4k" | "production" => Some(Quality::Production),
+            _ => None,
+        }
+    }
+    pub fn resolution(&self) -> (u32, u32) {
+        match self {
+            Quality::Low => (854, 480),
+            Quality::Medium => (1280, 720),
+            Quality::High => (1920, 1080),
+            Quality::Production => (3840, 2160),
+        }
+    }
+    pub fn fps(&self) -> f32 {
+        match self {
+            Quality::Low => 15.0,
+            Quality::Medium => 30.0,
+            Quality::High | Quality::Production => 60.0,
+        }
+    }
+}
+
+/// Output written by `Mode::Render`
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Format {
+    Png,
+    Video,
+    Svg,
+}
+
+impl Format {
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "png" => Some(Format::Png),
+            "video" | "mp4" | "webm" => Some(Format::Video),
+            "svg" => Some(Format::Svg),
+            _ => None,
+        }
+    }
+    /// Guess format from the extension of output path
+    pub fn guess(out: &Path) -> Self {
+        match out.extension().and_then(|ext| ext.to_str()) {
+            Some("mp4") | Some("webm") | Some("mov") | Some("mkv") | Some("gif") => Format::Video,
+            Some("svg") => Format::Svg,
+            _ => Format::Png,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Options {
+    pub mode: Mode,
+    pub scene: Option<String>,
+    pub from: f32,
+    pub to: Option<f32>,
+    pub quality: Option<Quality>,
+    pub resolution: Option<(u32, u32)>,
+    pub fps: Option<f32>,
+    pub format: Option<Format>,
+    pub out: PathBuf,
+    pub help: bool,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Options {
+    pub fn new() -> Self {
+        Self {
+            mode: Mode::Preview,
+            scene: None,
+            from: 0.0,
+            to: None,
+            quality: None,
+            resolution: None,
+            fps: None,
+            format: None,
+            out: PathBuf::from("out"),
+            help: false,
+        }
+    }
+    pub fn format(&self) -> Format {
+        self.format.unwrap_or_else(|| Format::guess(&self.out))
+    }
+    /// Build render settings from quality preset and overrides.
+    /// Without any quality option, preview keeps the default window size.
+    pub fn settings(&self, default: RenderSettings) -> RenderSettings {
+        let mut settings = default;
+        if let Some(quality) = self.quality {
+            let (width, height) = quality.resolution();
+            settings = RenderSettings::new(width, height).fps(quality.fps());
+        }
+        if let Some((width, height)) = self.resolution {
+            settings.width = width;
+            settings.height = height;
+        }
+        if let Some(fps) = self.fps {
+            settings = settings.fps(fps);
+        }
+        settings.range(self.from, self.to)
+    }
+}
+
+/// Parse arguments, excluding the program name
+pub fn parse<I>(args: I) -> Result<Options, String>
+where
+    I: IntoIterator<Item = String>,
+{
+    let mut options = Options::new();
+    let mut args = args.into_iter();
+
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| {
+            args.next()
+                .ok_or_else(|| format!("Missing value for {}", name))
+        };
+        match arg.as_str() {
+            "preview" => options.mode = Mode::Preview,
+            "render" => options.mode = Mode::Render,
+            "list" => options.mode = Mode::List,
+            "-h" | "--help" => options.help = true,
+            "-s" | "--scene" => options.scene = Some(value(&arg)?),
+            "--from" => options.from = parse_number(&arg, &value(&arg)?)?,
+            "--to" => options.to = Some(parse_number(&arg, &value(&arg)?)?),
+            "--fps" => {
+                let fps = parse_number(&arg, &value(&arg)?)?;
+                // Frames are stepped by 1 / fps
+                if fps.is_nan() || fps <= 0.0 {
+                    return Err(format!("--fps must be positive, got {}", fps));
+                }
+                options.fps = Some(fps);
+            }
+            "-q" | "--quality" => {
+                let name = value(&arg)?;
+                let quality =
+                    Quality::parse(&name).ok_or_else(|| format!("Unknown quality '{}'", name))?;
+                options.quality = Some(quality);
+            }
+            "-r" | "--resolution" => {
+                let size = value(&arg)?;
+                options.resolution = Some(parse_resolution(&size)?);
+            }
+            "-f" | "--format" => {
+                let name = value(&arg)?;
+                let format =
+                    Format::parse(&name).ok_or_else(|| format!("Unknown format '{}'", name))?;
+                options.format = Some(format);
+            }
+            "-o" | "--out" => options.out = PathBuf::from(value(&arg)?),
+            _ => return Err(format!("Unknown argument '{}'", arg)),
+        }
+    }
+    if let Some(to) = options.to {
+        if to < options.from {
+            return Err(format!("--to ({}) is before --from ({})", to, options.from));
+        }
+    }
+    Ok(options)
+}
+
+fn parse_number(name: &str, value: &str) -> Result<f32, String> {
+    value
+        .parse::<f32>()
+        .map_err(|_| format!("Invalid number '{}' for {}", value, name))
+}
+
+fn parse_resolution(value: &str) -> Result<(u32, u32), String> {
+    let mut parts = value.split('x');
+    match (
+        parts.next().and_then(|w| w.parse().ok()),
+        parts.next().and_then(|h| h.parse().ok()),
+        parts.next(),
+    ) {
+        (Some(width), Some(height), None) => Ok((width, height)),
+        _ => Err(format!("Invalid resolution '{}', expected WxH", value)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(line: &str) -> Vec<String> {
+        line.split_whitespace().map(String::from).collect()
+    }
+
+    #[test]
+    fn parse_render() {
+        let options = parse(args(
+            "render --scene Intro --from 2.0 --to 5.5 --fps 60 --out out/",
+        ))
+        .unwrap();
+        assert_eq!(options.mode, Mode::Render);
+        assert_eq!(options.scene, Some(String::from("Intro")));
+        assert_eq!(options.from, 2.0);
+        assert_eq!(options.to, Some(5.5));
+        assert_eq!(options.fps, Some(60.0));
+        assert_eq!(options.format(), Format::Png);
+
+        let settings = options.settings(RenderSettings::new(640, 480));
+        assert_eq!((settings.width, settings.height), (640, 480));
+        assert_eq!(settings.fps, 60.0);
+    }
+
+    #[test]
+    fn parse_quality() {
+        let options = parse(args("render -q h -o intro.mp4")).unwrap();
+        assert_eq!(options.format(), Format::Video);
+
+        let settings = options.settings(RenderSettings::new(640, 480));
+        assert_eq!((settings.width, settings.height), (1920, 1080));
+        assert_eq!(settings.fps, 60.0);
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert!(parse(args("render --fps")).is_err());
+        assert!(parse(args("render --quality ultra")).is_err());
+        assert!(parse(args("render --resolution 640")).is_err());
+        assert!(parse(args("render --from 3 --to 1")).is_err());
+        assert!(parse(args("render --fps 0")).is_err());
+        assert!(parse(args("render --fps -30")).is_err());
+        assert!(parse(args("render --fps NaN")).is_err());
+    }
+}
@@ -6,6 +6,12 @@ use std::marker::PhantomData;
 
 pub const DEFAULT_FLATTEN_TOLERANCE: f32 = 0.01;
 pub const DEFAULT_RUNTIME: f32 = 1.0;
+pub const DEFAULT_FPS: f32 = 30.0;
+pub const MORPH_SAMPLES: usize = 128; // Points per polygon when morphing shapes
+pub const WRITE_SPAN: f32 = 0.3; // Portion of `Write` run time each glyph takes
+pub const DEFAULT_WINDOW_WIDTH: u32 = 640;
+pub const DEFAULT_WINDOW_HEIGHT: u32 = 480;
+pub const DEFAULT_SUPERSAMPLING: u32 = 2;
 
 // Shorthand notation for directions
 // pub const ORIGIN: Vector = Vector::new(0.0, 0.0);
@@ -30,6 +36,14 @@ pub const LARGE_BUFF: f32 = 0.5;
 
 pub const DEFAULT_STROKE_WEIGHT: f32 = 3.0;
 pub const DEFAULT_TEXT_STROKE_WEIGHT: f32 = 1.0;
+pub const DEFAULT_DOT_RADIUS: f32 = 4.0;
+pub const DEFAULT_ARC_RADIUS: f32 = 20.0;
+pub const DEFAULT_POLYGON_RADIUS: f32 = 20.0;
+pub const DEFAULT_TIP_LENGTH: f32 = 12.0;
+pub const DEFAULT_TIP_SEGMENTS: usize = 16; // Segments of round arrow tip
+pub const SVG_GRADIENT_STEPS: usize = 8; // Stops per gradient segment in SVG export
+pub const DEFAULT_TEX_SCALE: f32 = 6.0; // Pixels per TeX point
+pub const TEXT_LAYOUT_CACHE_SIZE: usize = 256; // Text layouts kept for reuse
 
 pub const DEFAULT_FILL_COLOR: Rgb = RED_D;
 // pub const DEFAULT_FILL_COLOR: Rgb = Rgb {
@@ -1,3 +1,326 @@
+use crate::appearance::{GetColor, GetOpacity, Paint};
+use crate::consts::*;
+use crate::geom::{point_at, Point};
+
+use nannou;
+use nannou::color::Rgba;
+use nannou::lyon::math as lyon;
+use nannou::lyon::path::Path;
+use nannou::lyon::tessellation::{
+    BuffersBuilder, FillAttributes, FillOptions, FillTessellator, StrokeAttributes, StrokeOptions,
+    StrokeTessellator, VertexBuffers,
+};
+
+/// Placement of an object's local path in the scene.
+/// Orientation is in degrees, same as `SetOrientation`. Scale is uniform,
+/// and only differs from 1 for groups.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Transform {
+    pub position: Point,
+    pub orientation: f32,
+    pub scale: f32,
+}
+
+impl Default for Transform {
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+impl Transform {
+    pub fn new(position: Point, orientation: f32) -> Self {
+        Self {
+            position,
+            orientation,
+            scale: 1.0,
+        }
+    }
+    pub fn identity() -> Self {
+        Self::new(point_at(0.0, 0.0), 0.0)
+    }
+    pub fn with_scale(mut self, scale: f32) -> Self {
+        self.scale = scale;
+        self
+    }
+    /// Map a point from object-local coordinates to scene coordinates
+    pub fn apply(&self, p: lyon::Point) -> lyon::Point {
+        let (sin, cos) = self.orientation.to_radians().sin_cos();
+        let (x, y) = (p.x * self.scale, p.y * self.scale);
+        lyon::point(
+            x * cos - y * sin + self.position.x,
+            x * sin + y * cos + self.position.y,
+        )
+    }
+    /// Transform placing this one inside the space of `parent`
+    pub fn then(&self, parent: &Transform) -> Transform {
+        let position = parent.apply(self.position.into());
+        Self {
+            position: position.into(),
+            orientation: self.orientation + parent.orientation,
+            scale: self.scale * parent.scale,
+        }
+    }
+    pub fn inverse(&self) -> Transform {
+        let scale = if self.scale != 0.0 {
+            1.0 / self.scale
+        } else {
+            0.0
+        };
+        let rotation = Self::new(point_at(0.0, 0.0), -self.orientation).with_scale(scale);
+        let position = rotation.apply((self.position * -1.0).into());
+        Self {
+            position: position.into(),
+            orientation: -self.orientation,
+            scale,
+        }
+    }
+}
+
+/// Drawing target for objects. Implemented by nannou's `Draw` for the
+/// live window, and by offline renderers.
+pub trait Backend {
+    fn fill(&mut self, path: &Path, transform: &Transform, color: Rgba);
+    fn stroke(&mut self, path: &Path, transform: &Transform, color: Rgba, weight: f32);
+    /// Fill with a paint placed in object-local coordinates (see `Paint::fit`).
+    /// Backends without gradients fill with its representative color.
+    fn fill_paint(&mut self, path: &Path, transform: &Transform, paint: &Paint, alpha: f32) {
+        let color = Rgba {
+            color: paint.color(),
+            alpha,
+        };
+        self.fill(path, transform, color);
+    }
+    fn stroke_paint(
+        &mut self,
+        path: &Path,
+        transform: &Transform,
+        paint: &Paint,
+        alpha: f32,
+        weight: f32,
+    ) {
+        let color = Rgba {
+            color: paint.color(),
+            alpha,
+        };
+        self.stroke(path, transform, color, weight);
+    }
+}
+
+impl Backend for nannou::Draw {
+    fn fill(&mut self, path: &Path, transform: &Transform, color: Rgba) {
+        placed(self, transform)
+            .path()
+            .fill()
+            .color(color)
+            .events(path);
+    }
+    fn stroke(&mut self, path: &Path, transform: &Transform, color: Rgba, weight: f32) {
+        placed(self, transform)
+            .path()
+            .stroke()
+            .color(color)
+            .stroke_weight(weight)
+            .events(path);
+    }
+    fn fill_paint(&mut self, path: &Path, transform: &Transform, paint: &Paint, alpha: f32) {
+        match paint {
+            Paint::Solid(color) => self.fill(
+                path,
+                transform,
+                Rgba {
+                    color: *color,
+                    alpha,
+                },
+            ),
+            Paint::Gradient(_) => {
+                let geometry = fill_geometry(path);
+                draw_shaded(self, &geometry, transform, paint, alpha);
+            }
+        }
+    }
+    fn stroke_paint(
+        &mut self,
+        path: &Path,
+        transform: &Transform,
+        paint: &Paint,
+        alpha: f32,
+        weight: f32,
+    ) {
+        match paint {
+            Paint::Solid(color) => self.stroke(
+                path,
+                transform,
+                Rgba {
+                    color: *color,
+                    alpha,
+                },
+                weight,
+            ),
+            Paint::Gradient(_) => {
+                let geometry = stroke_geometry(path, weight);
+                draw_shaded(self, &geometry, transform, paint, alpha);
+            }
+        }
+    }
+}
+
+/// Paints and weight an object's outline is drawn with
+#[derive(Debug, Clone, PartialEq)]
+pub struct PathStyle {
+    pub fill: Paint,
+    pub stroke: Paint,
+    pub weight: f32,
+    pub alpha: f32,
+    /// Open outlines, like arcs, are only stroked
+    pub filled: bool,
+}
+
+impl PathStyle {
+    pub fn of<O: GetColor + GetOpacity>(object: &O) -> Self {
+        Self {
+            fill: object.fill_paint(),
+            stroke: object.stroke_paint(),
+            weight: object.stroke_weight(),
+            alpha: object.alpha(),
+            filled: true,
+        }
+    }
+    pub fn filled(mut self, filled: bool) -> Self {
+        self.filled = filled;
+        self
+    }
+    /// Place gradients over the whole `outline`, so they stay put while only
+    /// part of it is drawn
+    pub fn fit(mut self, outline: &Path) -> Self {
+        self.fill = self.fill.fit(outline);
+        self.stroke = self.stroke.fit(outline);
+        self
+    }
+    pub fn fill<B: Backend>(&self, backend: &mut B, path: &Path, transform: &Transform) {
+        if self.filled {
+            backend.fill_paint(path, transform, &self.fill, self.alpha);
+        }
+    }
+    pub fn stroke<B: Backend>(&self, backend: &mut B, path: &Path, transform: &Transform) {
+        backend.stroke_paint(path, transform, &self.stroke, self.alpha, self.weight);
+    }
+}
+
+/// Draw `path`, the part of `outline` reached so far, fill first and stroke
+/// on top
+pub fn draw_outline<B: Backend>(
+    backend: &mut B,
+    outline: &Path,
+    path: &Path,
+    transform: &Transform,
+    style: PathStyle,
+) {
+    let style = style.fit(outline);
+    style.fill(backend, path, transform);
+    style.stroke(backend, path, transform);
+}
+
+/// Triangles of a tessellated path, in the coordinates of the path
+pub type Geometry = VertexBuffers<lyon::Point, u32>;
+
+pub fn fill_geometry(path: &Path) -> Geometry {
+    let mut geometry: Geometry = VertexBuffers::new();
+    let options = FillOptions::tolerance(DEFAULT_FLATTEN_TOLERANCE);
+    let _ = FillTessellator::new().tessellate_path(
+        path,
+        &options,
+        &mut BuffersBuilder::new(&mut geometry, |p: lyon::Point, _: FillAttributes| p),
+    );
+    geometry
+}
+
+pub fn stroke_geometry(path: &Path, weight: f32) -> Geometry {
+    let mut geometry: Geometry = VertexBuffers::new();
+    let options = StrokeOptions::tolerance(DEFAULT_FLATTEN_TOLERANCE).with_line_width(weight);
+    let _ = StrokeTessellator::new().tessellate_path(
+        path,
+        &options,
+        &mut BuffersBuilder::new(&mut geometry, |p: lyon::Point, _: StrokeAttributes| p),
+    );
+    geometry
+}
+
+// Mesh colored per vertex, so gradients are blended across triangles
+fn draw_shaded(
+    draw: &nannou::Draw,
+    geometry: &Geometry,
+    transform: &Transform,
+    paint: &Paint,
+    alpha: f32,
+) {
+    let vertices = geometry.vertices.iter().map(|p| {
+        let color = Rgba {
+            color: paint.at(*p),
+            alpha,
+        };
+        (nannou::geom::pt3(p.x, p.y, 0.0), color)
+    });
+    let indices = geometry.indices.iter().map(|i| *i as usize);
+    placed(draw, transform)
+        .mesh()
+        .indexed_colored(vertices, indices);
+}
+
+// Draw context with origin moved to placement of object
+fn placed(draw: &nannou::Draw, transform: &Transform) -> nannou::Draw {
+    draw.x_y(transform.position.x, transform.position.y)
+        .z_degrees(transform.orientation)
+        .scale(transform.scale)
+}
+
+/// Backend drawing into the space of a parent object, e.g. a group.
+/// Transforms are placed inside `parent`, and alpha is multiplied by `alpha`.
+pub struct Nested<'a, B: Backend> {
+    backend: &'a mut B,
+    parent: Transform,
+    alpha: f32,
+}
+
+impl<'a, B: Backend> Nested<'a, B> {
+    pub fn new(backend: &'a mut B, parent: Transform, alpha: f32) -> Self {
+        Self {
+            backend,
+            parent,
+            alpha,
+        }
+    }
+}
+
+impl<'a, B: Backend> Backend for Nested<'a, B> {
+    fn fill(&mut self, path: &Path, transform: &Transform, mut color: Rgba) {
+        color.alpha *= self.alpha;
+        let transform = transform.then(&self.parent);
+        self.backend.fill(path, &transform, color);
+    }
+    fn stroke(&mut self, path: &Path, transform: &Transform, mut color: Rgba, weight: f32) {
+        color.alpha *= self.alpha;
+        let transform = transform.then(&self.parent);
+        self.backend.stroke(path, &transform, color, weight);
+    }
+    fn fill_paint(&mut self, path: &Path, transform: &Transform, paint: &Paint, alpha: f32) {
+        let transform = transform.then(&self.parent);
+        self.backend
+            .fill_paint(path, &transform, paint, alpha * self.alpha);
+    }
+    fn stroke_paint(
+        &mut self,
+        path: &Path,
+        transform: &Transform,
+        paint: &Paint,
+        alpha: f32,
+        weight: f32,
+    ) {
+        let transform = transform.then(&self.parent);
+        self.backend
+            .stroke_paint(path, &transform, paint, alpha * self.alpha, weight);
+    }
+}
+
 pub trait Draw {
-    fn draw(&self, draw: nannou::Draw);
+    fn draw<B: Backend>(&self, backend: &mut B);
 }
@@ -1,8 +1,32 @@
 use pennereq::*;
 
+use std::cmp::Ordering;
+use std::f32::consts::PI;
+use std::fmt;
+use std::sync::Arc;
+
 type EaseFn<S = f32> = fn(t: S, b: S, c: S, d: S) -> S;
 
-#[derive(Debug, Copy, Clone, PartialEq)]
+/// User defined rate, mapping progress in time to progress of the action.
+/// Rates are compared by identity, as closures can't be compared.
+#[derive(Clone)]
+pub struct RateFunc(Arc<dyn Fn(f32) -> f32 + Send + Sync>);
+
+impl fmt::Debug for RateFunc {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "RateFunc")
+    }
+}
+
+impl PartialEq for RateFunc {
+    fn eq(&self, other: &Self) -> bool {
+        let a = &*self.0 as *const _ as *const u8;
+        let b = &*other.0 as *const _ as *const u8;
+        a == b
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
 pub enum EaseType {
     Linear,
     Quad,
@@ -35,11 +59,54 @@ pub enum EaseType {
     Bounce,
     BounceIn,
     BounceOut,
+    Custom(RateFunc),
 }
 
 impl EaseType {
+    /// Rate given by a closure, taking and returning values from 0 to 1
+    ///
+    /// ```ignore
+    /// scene.play(c.move_by(RIGHT * 100.0)).rate_func(EaseType::custom(|t| t * t));
+    /// ```
+    pub fn custom<F>(f: F) -> Self
+    where
+        F: Fn(f32) -> f32 + Send + Sync + 'static,
+    {
+        EaseType::Custom(RateFunc(Arc::new(f)))
+    }
+    /// Same rate played backwards, ending where it started
+    pub fn reverse(self) -> Self {
+        Self::custom(move |t| self.calculate(1.0 - t))
+    }
+    /// Rate played to the end in the first half and back in the second
+    pub fn there_and_back(self) -> Self {
+        Self::custom(move |t| {
+            if t < 0.5 {
+                self.calculate(2.0 * t)
+            } else {
+                self.calculate(2.0 * (1.0 - t))
+            }
+        })
+    }
+    /// Rate squeezed between `a` and `b`, holding still before and after
+    pub fn squish(self, a: f32, b: f32) -> Self {
+        Self::custom(move |t| {
+            if t <= a || b <= a {
+                self.calculate(0.0)
+            } else if t >= b {
+                self.calculate(1.0)
+            } else {
+                self.calculate((t - a) / (b - a))
+            }
+        })
+    }
+    /// Rate applied to the result of `inner`
+    pub fn compose(self, inner: EaseType) -> Self {
+        Self::custom(move |t| self.calculate(inner.calculate(t)))
+    }
     pub fn calculate(&self, t: f32) -> f32 {
         let ease_func: EaseFn = match self {
+            EaseType::Custom(f) => return (f.0)(t),
             EaseType::Linear => linear::ease,
             EaseType::Quad => quad::ease_in_out,
             EaseType::QuadIn => quad::ease_in,
@@ -75,6 +142,71 @@ impl EaseType {
         ease_func(t, 0.0, 1.0, 1.0)
     }
 }
+
+/// Rate of a CSS `cubic-bezier` timing function, with control points
+/// between (0, 0) and (1, 1)
+pub fn cubic_bezier(x1: f32, y1: f32, x2: f32, y2: f32) -> EaseType {
+    // Coordinate of the curve at parameter `s`, for control points `p1` and `p2`
+    let bezier = |p1: f32, p2: f32, s: f32| {
+        let u = 1.0 - s;
+        3.0 * u * u * s * p1 + 3.0 * u * s * s * p2 + s * s * s
+    };
+    let x1 = x1.max(0.0).min(1.0);
+    let x2 = x2.max(0.0).min(1.0);
+    EaseType::custom(move |t| {
+        if t <= 0.0 || t >= 1.0 {
+            return t;
+        }
+        // x is increasing in s, so bisect for the parameter reaching t
+        let (mut low, mut high) = (0.0, 1.0);
+        for _ in 0..32 {
+            let mid = (low + high) / 2.0;
+            if bezier(x1, x2, mid) < t {
+                low = mid;
+            } else {
+                high = mid;
+            }
+        }
+        bezier(y1, y2, (low + high) / 2.0)
+    })
+}
+
+/// Rate moving linearly between `(time, progress)` keyframes, holding the
+/// first and last progress outside of them. Keyframes with a NaN time or
+/// progress are left out.
+///
+/// ```ignore
+/// let rate = keyframes(&[(0.0, 0.0), (0.2, 0.8), (0.8, 0.8), (1.0, 1.0)]);
+/// ```
+pub fn keyframes(frames: &[(f32, f32)]) -> EaseType {
+    let mut frames: Vec<(f32, f32)> = frames
+        .iter()
+        .filter(|(time, progress)| !time.is_nan() && !progress.is_nan())
+        .cloned()
+        .collect();
+    // Times without NaN are totally ordered
+    frames.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(Ordering::Equal));
+    EaseType::custom(move |t| {
+        let next = frames.iter().position(|frame| frame.0 > t);
+        match next {
+            None => frames.last().map(|frame| frame.1).unwrap_or(t),
+            Some(0) => frames[0].1,
+            Some(i) => {
+                let (t0, p0) = frames[i - 1];
+                let (t1, p1) = frames[i];
+                p0 + (p1 - p0) * (t - t0) / (t1 - t0)
+            }
+        }
+    })
+}
+
+/// Rate swinging back and forth `wiggles` times around the start, like
+/// manim's `wiggle`
+pub fn wiggle(wiggles: f32) -> EaseType {
+    let envelope = EaseType::Quad.there_and_back();
+    EaseType::custom(move |t| envelope.calculate(t) * (wiggles * PI * t).sin())
+}
+
 // Linear takes normalized time and returns unmodified value.
 // This is the default ease function in addition to pennereq crate.
 pub mod linear {
@@ -83,3 +215,42 @@ pub mod linear {
         t
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn custom_rates() {
+        let close = |a: f32, b: f32| (a - b).abs() < 1e-4;
+        let linear = cubic_bezier(0.0, 0.0, 1.0, 1.0);
+        assert!(close(linear.calculate(0.3), 0.3));
+        let ease = cubic_bezier(0.25, 0.1, 0.25, 1.0);
+        assert!(ease.calculate(0.5) > 0.5);
+
+        let frames = keyframes(&[(0.0, 0.0), (0.5, 1.0), (1.0, 0.5)]);
+        assert!(close(frames.calculate(0.25), 0.5));
+        assert!(close(frames.calculate(0.75), 0.75));
+        let nan = std::f32::NAN;
+        let frames = keyframes(&[(1.0, 0.5), (nan, 0.9), (0.0, 0.0), (0.5, nan), (0.5, 1.0)]);
+        assert!(close(frames.calculate(0.25), 0.5));
+        assert!(close(frames.calculate(2.0), 0.5));
+        assert!(keyframes(&[(nan, nan)]).calculate(0.3) == 0.3);
+
+        let back = EaseType::Linear.there_and_back();
+        assert!(close(back.calculate(0.25), 0.5));
+        assert!(close(back.calculate(1.0), 0.0));
+        let squished = EaseType::Linear.squish(0.5, 1.0);
+        assert!(close(squished.calculate(0.25), 0.0));
+        assert!(close(squished.calculate(0.75), 0.5));
+        let reversed = EaseType::Linear.reverse();
+        assert!(close(reversed.calculate(0.25), 0.75));
+        let composed = EaseType::custom(|t| t * t).compose(EaseType::Linear.reverse());
+        assert!(close(composed.calculate(0.5), 0.25));
+        assert!(close(wiggle(2.0).calculate(1.0), 0.0));
+
+        // Closures only equal themselves
+        assert_eq!(back.clone(), back);
+        assert_ne!(back, EaseType::Linear.there_and_back());
+    }
+}
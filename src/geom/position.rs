@@ -1,5 +1,5 @@
 use crate::animation::{lerp, Interpolate};
-use std::ops::Add;
+use std::ops::{Add, Mul, Sub};
 
 use nannou::lyon::math as lyon;
 
@@ -71,6 +71,26 @@ impl Add for Point {
     }
 }
 
+impl Sub for Point {
+    type Output = Self;
+    fn sub(self, other: Self) -> Self {
+        Self {
+            x: self.x - other.x,
+            y: self.y - other.y,
+        }
+    }
+}
+
+impl Mul<f32> for Point {
+    type Output = Self;
+    fn mul(self, scale: f32) -> Self {
+        Self {
+            x: self.x * scale,
+            y: self.y * scale,
+        }
+    }
+}
+
 pub trait SetPosition: GetPosition {
     fn position_mut(&mut self) -> &mut Point;
 
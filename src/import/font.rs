@@ -0,0 +1,152 @@
+use nannou::text::Font;
+
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// Failure to load a font file
+#[derive(Debug, Clone, PartialEq)]
+pub struct FontError {
+    pub file: PathBuf,
+    pub message: String,
+}
+
+impl fmt::Display for FontError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Failed to load font {}: {}",
+            self.file.display(),
+            self.message
+        )
+    }
+}
+
+impl std::error::Error for FontError {}
+
+/// Thickness of the strokes of a face, like manim's `weight`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Weight {
+    Normal,
+    Bold,
+}
+
+/// Posture of a face, like manim's `slant`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Slant {
+    Normal,
+    Italic,
+}
+
+// Font of a family with its weight and slant. Faces are compared by the
+// file they come from, as fonts themselves can't be compared.
+#[derive(Clone)]
+struct Face {
+    weight: Weight,
+    slant: Slant,
+    source: String,
+    font: Font,
+}
+
+impl fmt::Debug for Face {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Face")
+            .field("weight", &self.weight)
+            .field("slant", &self.slant)
+            .field("source", &self.source)
+            .finish()
+    }
+}
+
+impl PartialEq for Face {
+    fn eq(&self, other: &Self) -> bool {
+        (self.weight, self.slant, &self.source) == (other.weight, other.slant, &other.source)
+    }
+}
+
+/// Faces of one typeface, loaded from TTF or OTF files.
+///
+/// ```ignore
+/// let fira = FontFamily::new("Fira Sans")
+///     .face(Weight::Normal, Slant::Normal, "FiraSans-Regular.ttf")?
+///     .face(Weight::Bold, Slant::Normal, "FiraSans-Bold.ttf")?;
+/// scene.act(t.set_font(fira));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct FontFamily {
+    name: String,
+    faces: Vec<Face>,
+}
+
+impl Default for FontFamily {
+    /// Noto Sans bundled with nannou, in normal weight and slant only
+    fn default() -> Self {
+        Self::new("Noto Sans").with_font(
+            Weight::Normal,
+            Slant::Normal,
+            "default",
+            nannou::text::font::default_notosans(),
+        )
+    }
+}
+
+impl FontFamily {
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: String::from(name),
+            faces: Vec::new(),
+        }
+    }
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+    /// Add a face read from `file`, replacing one of the same weight and slant
+    pub fn face<P: AsRef<Path>>(
+        self,
+        weight: Weight,
+        slant: Slant,
+        file: P,
+    ) -> Result<Self, FontError> {
+        let file = file.as_ref();
+        let font = nannou::text::font::from_file(file).map_err(|e| FontError {
+            file: file.to_path_buf(),
+            message: e.to_string(),
+        })?;
+        Ok(self.with_font(weight, slant, &file.to_string_lossy(), font))
+    }
+    fn with_font(mut self, weight: Weight, slant: Slant, source: &str, font: Font) -> Self {
+        self.faces
+            .retain(|face| (face.weight, face.slant) != (weight, slant));
+        self.faces.push(Face {
+            weight,
+            slant,
+            source: String::from(source),
+            font,
+        });
+        self
+    }
+    /// Font closest to `weight` and `slant`. Slant is kept over weight,
+    /// and families without faces fall back to the default font.
+    pub fn select(&self, weight: Weight, slant: Slant) -> Font {
+        self.closest(weight, slant)
+            .map(|face| face.font.clone())
+            .unwrap_or_else(nannou::text::font::default_notosans)
+    }
+    /// File of the font chosen by `select`, which identifies it
+    pub fn source(&self, weight: Weight, slant: Slant) -> &str {
+        self.closest(weight, slant)
+            .map(|face| face.source.as_str())
+            .unwrap_or("default")
+    }
+    fn closest(&self, weight: Weight, slant: Slant) -> Option<&Face> {
+        let exact = self
+            .faces
+            .iter()
+            .find(|face| (face.weight, face.slant) == (weight, slant));
+        let same_slant = || self.faces.iter().find(|face| face.slant == slant);
+        let same_weight = || self.faces.iter().find(|face| face.weight == weight);
+        exact
+            .or_else(same_slant)
+            .or_else(same_weight)
+            .or_else(|| self.faces.first())
+    }
+}
@@ -0,0 +1,8 @@
+//! Loading of vector art and fonts made by other programs
+pub use self::font::{FontError, FontFamily, Slant, Weight};
+pub use self::svg::SvgError;
+pub use self::tex::{TexError, TexRenderer};
+
+pub mod font;
+pub mod svg;
+pub mod tex;
@@ -0,0 +1,959 @@
+use nannou::lyon::math::{point, Point};
+use nannou::lyon::path::builder::PathBuilder;
+use nannou::lyon::path::Path;
+
+use nannou::color::Rgb;
+
+use std::collections::HashMap;
+use std::f32::consts::PI;
+use std::fmt;
+use std::fs;
+
+/// Failure to read an SVG document
+#[derive(Debug, Clone, PartialEq)]
+pub enum SvgError {
+    Io(String),
+    Xml(String),
+    PathData(String),
+}
+
+impl fmt::Display for SvgError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SvgError::Io(message) => write!(f, "Failed to read SVG file {}", message),
+            SvgError::Xml(message) => write!(f, "Invalid SVG document: {}", message),
+            SvgError::PathData(message) => write!(f, "Invalid SVG path data: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for SvgError {}
+
+/// Element of an SVG document, without its text content
+#[derive(Debug, Clone, PartialEq)]
+pub struct Element {
+    pub name: String,
+    pub attributes: HashMap<String, String>,
+    pub children: Vec<Element>,
+}
+
+impl Element {
+    pub fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes.get(name).map(|value| value.as_str())
+    }
+    // Numeric attribute, with units like `pt` or `px` ignored
+    fn number(&self, name: &str) -> f32 {
+        self.attribute(name)
+            .and_then(|value| {
+                let value = value.trim_end_matches(|c: char| c.is_ascii_alphabetic() || c == '%');
+                value.trim().parse().ok()
+            })
+            .unwrap_or(0.0)
+    }
+    fn href(&self) -> Option<&str> {
+        self.attribute("xlink:href")
+            .or_else(|| self.attribute("href"))
+            .map(|href| href.trim_start_matches('#'))
+    }
+}
+
+/// Parse an SVG document into its root element
+pub fn parse(source: &str) -> Result<Element, SvgError> {
+    let mut stack: Vec<Element> = Vec::new();
+    let mut root = None;
+    let mut rest = source;
+    while let Some(start) = rest.find('<') {
+        rest = &rest[start..];
+        if rest.starts_with("<!--") {
+            rest = skip_past(rest, "-->")?;
+        } else if rest.starts_with("<![CDATA[") {
+            rest = skip_past(rest, "]]>")?;
+        } else if rest.starts_with("<?") || rest.starts_with("<!") {
+            rest = skip_past(rest, ">")?;
+        } else if rest.starts_with("</") {
+            rest = skip_past(rest, ">")?;
+            let element = stack
+                .pop()
+                .ok_or_else(|| SvgError::Xml(String::from("unexpected closing tag")))?;
+            match stack.last_mut() {
+                Some(parent) => parent.children.push(element),
+                None => root = Some(element),
+            }
+        } else {
+            let end = tag_end(rest)?;
+            let tag = &rest[1..end];
+            rest = &rest[end + 1..];
+            let empty = tag.ends_with('/');
+            let element = element(tag.trim_end_matches('/'))?;
+            if empty {
+                match stack.last_mut() {
+                    Some(parent) => parent.children.push(element),
+                    None => root = Some(element),
+                }
+            } else {
+                stack.push(element);
+            }
+        }
+    }
+    if !stack.is_empty() {
+        return Err(SvgError::Xml(format!("unclosed <{}>", stack[0].name)));
+    }
+    root.ok_or_else(|| SvgError::Xml(String::from("no root element")))
+}
+
+fn skip_past<'a>(source: &'a str, end: &str) -> Result<&'a str, SvgError> {
+    source
+        .find(end)
+        .map(|i| &source[i + end.len()..])
+        .ok_or_else(|| SvgError::Xml(format!("missing '{}'", end)))
+}
+
+// Index of the `>` closing a tag, skipping quoted attribute values
+fn tag_end(source: &str) -> Result<usize, SvgError> {
+    let mut quote = None;
+    for (i, c) in source.char_indices() {
+        match (quote, c) {
+            (None, '"') | (None, '\'') => quote = Some(c),
+            (Some(q), _) if q == c => quote = None,
+            (None, '>') => return Ok(i),
+            _ => (),
+        }
+    }
+    Err(SvgError::Xml(String::from("unterminated tag")))
+}
+
+// Name and attributes of a start tag, without the angle brackets
+fn element(tag: &str) -> Result<Element, SvgError> {
+    let tag = tag.trim();
+    let name_end = tag.find(char::is_whitespace).unwrap_or(tag.len());
+    let name = String::from(&tag[..name_end]);
+    let mut attributes = HashMap::new();
+    let mut rest = tag[name_end..].trim_start();
+    while !rest.is_empty() {
+        let eq = rest
+            .find('=')
+            .ok_or_else(|| SvgError::Xml(format!("attribute without value in <{}>", name)))?;
+        let key = rest[..eq].trim();
+        let value = rest[eq + 1..].trim_start();
+        let quote = value
+            .chars()
+            .next()
+            .filter(|c| *c == '"' || *c == '\'')
+            .ok_or_else(|| SvgError::Xml(format!("unquoted attribute in <{}>", name)))?;
+        let end = value[1..]
+            .find(quote)
+            .ok_or_else(|| SvgError::Xml(format!("unterminated attribute in <{}>", name)))?;
+        attributes.insert(String::from(key), unescape(&value[1..end + 1]));
+        rest = value[end + 2..].trim_start();
+    }
+    Ok(Element {
+        name,
+        attributes,
+        children: Vec::new(),
+    })
+}
+
+fn unescape(value: &str) -> String {
+    value
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+/// Affine transform of SVG, mapping (x, y) to (ax + cy + e, bx + dy + f)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Affine {
+    pub a: f32,
+    pub b: f32,
+    pub c: f32,
+    pub d: f32,
+    pub e: f32,
+    pub f: f32,
+}
+
+impl Affine {
+    pub fn identity() -> Self {
+        Self::new(1.0, 0.0, 0.0, 1.0, 0.0, 0.0)
+    }
+    pub fn new(a: f32, b: f32, c: f32, d: f32, e: f32, f: f32) -> Self {
+        Self { a, b, c, d, e, f }
+    }
+    pub fn translate(x: f32, y: f32) -> Self {
+        Self::new(1.0, 0.0, 0.0, 1.0, x, y)
+    }
+    pub fn scale(x: f32, y: f32) -> Self {
+        Self::new(x, 0.0, 0.0, y, 0.0, 0.0)
+    }
+    pub fn rotate(degrees: f32) -> Self {
+        let (sin, cos) = degrees.to_radians().sin_cos();
+        Self::new(cos, sin, -sin, cos, 0.0, 0.0)
+    }
+    pub fn apply(&self, p: Point) -> Point {
+        point(
+            self.a * p.x + self.c * p.y + self.e,
+            self.b * p.x + self.d * p.y + self.f,
+        )
+    }
+    /// Transform applying `inner` first, then this one
+    pub fn compose(&self, inner: &Affine) -> Affine {
+        Affine::new(
+            self.a * inner.a + self.c * inner.b,
+            self.b * inner.a + self.d * inner.b,
+            self.a * inner.c + self.c * inner.d,
+            self.b * inner.c + self.d * inner.d,
+            self.a * inner.e + self.c * inner.f + self.e,
+            self.b * inner.e + self.d * inner.f + self.f,
+        )
+    }
+    /// Parse a `transform` attribute, e.g. `translate(10 20) scale(2)`
+    pub fn parse(value: &str) -> Result<Self, SvgError> {
+        let mut transform = Affine::identity();
+        let mut rest = value.trim();
+        while !rest.is_empty() {
+            let open = rest
+                .find('(')
+                .ok_or_else(|| SvgError::Xml(format!("invalid transform '{}'", value)))?;
+            let close = rest[open..]
+                .find(')')
+                .map(|i| open + i)
+                .ok_or_else(|| SvgError::Xml(format!("invalid transform '{}'", value)))?;
+            let name = rest[..open].trim().trim_start_matches(',').trim();
+            let args = numbers(&rest[open + 1..close])?;
+            let arg = |i: usize, default: f32| args.get(i).cloned().unwrap_or(default);
+            let next = match name {
+                "matrix" if args.len() == 6 => {
+                    Affine::new(args[0], args[1], args[2], args[3], args[4], args[5])
+                }
+                "translate" => Affine::translate(arg(0, 0.0), arg(1, 0.0)),
+                "scale" => Affine::scale(arg(0, 1.0), arg(1, arg(0, 1.0))),
+                "rotate" => {
+                    let (x, y) = (arg(1, 0.0), arg(2, 0.0));
+                    Affine::translate(x, y)
+                        .compose(&Affine::rotate(arg(0, 0.0)))
+                        .compose(&Affine::translate(-x, -y))
+                }
+                "skewX" => Affine::new(1.0, 0.0, arg(0, 0.0).to_radians().tan(), 1.0, 0.0, 0.0),
+                "skewY" => Affine::new(1.0, arg(0, 0.0).to_radians().tan(), 0.0, 1.0, 0.0, 0.0),
+                _ => return Err(SvgError::Xml(format!("invalid transform '{}'", value))),
+            };
+            transform = transform.compose(&next);
+            rest = rest[close + 1..].trim_start();
+        }
+        Ok(transform)
+    }
+}
+
+fn numbers(value: &str) -> Result<Vec<f32>, SvgError> {
+    let mut scanner = Scanner::new(value);
+    let mut numbers = Vec::new();
+    while scanner.has_more() {
+        numbers.push(scanner.number()?);
+    }
+    Ok(numbers)
+}
+
+/// Shape of an SVG document with its resolved style
+#[derive(Debug, Clone)]
+pub struct Shape {
+    pub path: Path,
+    pub style: Style,
+}
+
+/// Presentation of a shape, inherited from its ancestors like in SVG
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Style {
+    pub fill: Option<Rgb>,
+    pub stroke: Option<Rgb>,
+    pub stroke_width: f32,
+    pub fill_opacity: f32,
+    pub stroke_opacity: f32,
+    pub opacity: f32, // Product of `opacity` of the shape and its ancestors
+    color: Rgb,       // Value of `currentColor`
+}
+
+impl Default for Style {
+    // Initial values of SVG, filled black without stroke
+    fn default() -> Self {
+        let black = Rgb::new(0.0, 0.0, 0.0);
+        Self {
+            fill: Some(black),
+            stroke: None,
+            stroke_width: 1.0,
+            fill_opacity: 1.0,
+            stroke_opacity: 1.0,
+            opacity: 1.0,
+            color: black,
+        }
+    }
+}
+
+impl Style {
+    // Style of `element` within a parent of this style. Declarations of
+    // the `style` attribute take precedence over presentation attributes.
+    fn of(&self, element: &Element) -> Self {
+        let mut properties: Vec<(&str, &str)> = element
+            .attributes
+            .iter()
+            .map(|(name, value)| (name.as_str(), value.as_str()))
+            .collect();
+        if let Some(style) = element.attribute("style") {
+            properties.extend(style.split(';').filter_map(|declaration| {
+                let colon = declaration.find(':')?;
+                Some((declaration[..colon].trim(), declaration[colon + 1..].trim()))
+            }));
+        }
+        let mut style = *self;
+        // `color` first, as other properties may refer to it
+        properties.sort_by_key(|(name, _)| *name != "color");
+        for (name, value) in properties {
+            let value = value.trim_end_matches("!important").trim();
+            if value == "inherit" {
+                continue;
+            }
+            let fraction = || value.parse::<f32>().ok().map(|v| v.max(0.0).min(1.0));
+            match name {
+                "color" => style.color = color(value, style.color).unwrap_or(style.color),
+                "fill" => style.fill = paint(value, style.color).unwrap_or(style.fill),
+                "stroke" => style.stroke = paint(value, style.color).unwrap_or(style.stroke),
+                "stroke-width" => style.stroke_width = length(value).unwrap_or(style.stroke_width),
+                "fill-opacity" => style.fill_opacity = fraction().unwrap_or(style.fill_opacity),
+                "stroke-opacity" => {
+                    style.stroke_opacity = fraction().unwrap_or(style.stroke_opacity)
+                }
+                "opacity" => style.opacity = self.opacity * fraction().unwrap_or(1.0),
+                _ => (),
+            }
+        }
+        style
+    }
+}
+
+// Value of `fill` or `stroke`, `None` if it can't be read
+fn paint(value: &str, current: Rgb) -> Option<Option<Rgb>> {
+    match value {
+        "none" | "transparent" => Some(None),
+        // Gradients and patterns are drawn with their fallback, if any
+        _ if value.starts_with("url(") => {
+            let fallback = value[value.find(')')? + 1..].trim();
+            color(fallback, current).map(Some)
+        }
+        _ => color(value, current).map(Some),
+    }
+}
+
+fn color(value: &str, current: Rgb) -> Option<Rgb> {
+    let rgb = |r: f32, g: f32, b: f32| Some(Rgb::new(r / 255.0, g / 255.0, b / 255.0));
+    let value = value.trim();
+    if value.starts_with('#') {
+        let hex = &value[1..];
+        let digit = |i: usize| u8::from_str_radix(&hex[i..i + 1], 16).ok().map(f32::from);
+        let byte = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok().map(f32::from);
+        return match hex.len() {
+            _ if !hex.is_ascii() => None,
+            3 => rgb(digit(0)? * 17.0, digit(1)? * 17.0, digit(2)? * 17.0),
+            6 => rgb(byte(0)?, byte(2)?, byte(4)?),
+            _ => None,
+        };
+    }
+    if value.starts_with("rgb(") && value.ends_with(')') {
+        let args = &value[4..value.len() - 1];
+        let channels: Vec<f32> = args
+            .split(|c| c == ',' || c == ' ')
+            .filter(|channel| !channel.is_empty())
+            .map(|channel| {
+                if channel.ends_with('%') {
+                    channel
+                        .trim_end_matches('%')
+                        .parse::<f32>()
+                        .map(|p| p * 2.55)
+                } else {
+                    channel.parse()
+                }
+            })
+            .collect::<Result<_, _>>()
+            .ok()?;
+        return match channels.as_slice() {
+            [r, g, b] => rgb(
+                r.max(0.0).min(255.0),
+                g.max(0.0).min(255.0),
+                b.max(0.0).min(255.0),
+            ),
+            _ => None,
+        };
+    }
+    match value.to_ascii_lowercase().as_str() {
+        "currentcolor" => Some(current),
+        "black" => rgb(0.0, 0.0, 0.0),
+        "white" => rgb(255.0, 255.0, 255.0),
+        "red" => rgb(255.0, 0.0, 0.0),
+        "lime" => rgb(0.0, 255.0, 0.0),
+        "green" => rgb(0.0, 128.0, 0.0),
+        "blue" => rgb(0.0, 0.0, 255.0),
+        "yellow" => rgb(255.0, 255.0, 0.0),
+        "cyan" | "aqua" => rgb(0.0, 255.0, 255.0),
+        "magenta" | "fuchsia" => rgb(255.0, 0.0, 255.0),
+        "gray" | "grey" => rgb(128.0, 128.0, 128.0),
+        "silver" => rgb(192.0, 192.0, 192.0),
+        "maroon" => rgb(128.0, 0.0, 0.0),
+        "olive" => rgb(128.0, 128.0, 0.0),
+        "navy" => rgb(0.0, 0.0, 128.0),
+        "purple" => rgb(128.0, 0.0, 128.0),
+        "teal" => rgb(0.0, 128.0, 128.0),
+        "orange" => rgb(255.0, 165.0, 0.0),
+        _ => None,
+    }
+}
+
+// Length with units like `px` ignored
+fn length(value: &str) -> Option<f32> {
+    value
+        .trim_end_matches(|c: char| c.is_ascii_alphabetic())
+        .trim()
+        .parse()
+        .ok()
+}
+
+/// Read the shapes of an SVG file
+pub fn load<P: AsRef<std::path::Path>>(file: P) -> Result<Vec<Shape>, SvgError> {
+    let source = fs::read_to_string(file.as_ref())
+        .map_err(|e| SvgError::Io(format!("{}: {}", file.as_ref().display(), e)))?;
+    shapes(&parse(&source)?)
+}
+
+/// Shapes of an SVG document in drawing order, each with the transforms of
+/// its ancestors applied. Coordinates stay in SVG space, with y pointing down.
+pub fn shapes(root: &Element) -> Result<Vec<Shape>, SvgError> {
+    let mut ids = HashMap::new();
+    collect_ids(root, &mut ids);
+    let mut shapes = Vec::new();
+    let parent = Context {
+        transform: Affine::identity(),
+        style: Style::default(),
+        depth: 0,
+    };
+    walk(root, &parent, &ids, &mut shapes)?;
+    Ok(shapes)
+}
+
+/// Outlines of the shapes in an SVG document, ignoring their style
+pub fn outlines(root: &Element) -> Result<Vec<Path>, SvgError> {
+    Ok(shapes(root)?.into_iter().map(|shape| shape.path).collect())
+}
+
+fn collect_ids<'a>(element: &'a Element, ids: &mut HashMap<&'a str, &'a Element>) {
+    if let Some(id) = element.attribute("id") {
+        ids.insert(id, element);
+    }
+    for child in element.children.iter() {
+        collect_ids(child, ids);
+    }
+}
+
+// References deeper than this are assumed to be cyclic
+const MAX_USE_DEPTH: usize = 32;
+
+// What an element inherits from its ancestors
+struct Context {
+    transform: Affine,
+    style: Style,
+    depth: usize, // Nesting of `use`
+}
+
+fn walk(
+    element: &Element,
+    parent: &Context,
+    ids: &HashMap<&str, &Element>,
+    shapes: &mut Vec<Shape>,
+) -> Result<(), SvgError> {
+    let transform = match element.attribute("transform") {
+        Some(value) => parent.transform.compose(&Affine::parse(value)?),
+        None => parent.transform,
+    };
+    let context = Context {
+        transform,
+        style: parent.style.of(element),
+        depth: parent.depth,
+    };
+    let number = |name: &str| element.number(name);
+    let path = match element.name.as_str() {
+        "svg" | "g" | "a" | "switch" => {
+            for child in element.children.iter() {
+                walk(child, &context, ids, shapes)?;
+            }
+            None
+        }
+        "use" => {
+            let target = element.href().and_then(|id| ids.get(id));
+            if let Some(target) = target {
+                if context.depth < MAX_USE_DEPTH {
+                    let offset = Affine::translate(number("x"), number("y"));
+                    let context = Context {
+                        transform: transform.compose(&offset),
+                        depth: context.depth + 1,
+                        ..context
+                    };
+                    // Symbols are only drawn through `use`
+                    if target.name == "symbol" {
+                        for child in target.children.iter() {
+                            walk(child, &context, ids, shapes)?;
+                        }
+                    } else {
+                        walk(target, &context, ids, shapes)?;
+                    }
+                }
+            }
+            None
+        }
+        "path" => match element.attribute("d") {
+            Some(data) => Some(path_data(data, &transform)?),
+            None => None,
+        },
+        "rect" => {
+            let (x, y) = (number("x"), number("y"));
+            let (w, h) = (number("width"), number("height"));
+            // A missing corner radius defaults to the other one
+            let (rx, ry) = match (element.attribute("rx"), element.attribute("ry")) {
+                (None, None) => (0.0, 0.0),
+                (Some(_), None) => (number("rx"), number("rx")),
+                (None, Some(_)) => (number("ry"), number("ry")),
+                _ => (number("rx"), number("ry")),
+            };
+            let (rx, ry) = (rx.min(w / 2.0), ry.min(h / 2.0));
+            if w > 0.0 && h > 0.0 && rx > 0.0 && ry > 0.0 {
+                let data = format!(
+                    "M{} {}H{}A{rx} {ry} 0 0 1 {} {}V{}A{rx} {ry} 0 0 1 {} {}H{}\
+                     A{rx} {ry} 0 0 1 {} {}V{}A{rx} {ry} 0 0 1 {} {}Z",
+                    x + rx,
+                    y,
+                    x + w - rx,
+                    x + w,
+                    y + ry,
+                    y + h - ry,
+                    x + w - rx,
+                    y + h,
+                    x + rx,
+                    x,
+                    y + h - ry,
+                    y + ry,
+                    x + rx,
+                    y,
+                    rx = rx,
+                    ry = ry
+                );
+                Some(path_data(&data, &transform)?)
+            } else if w > 0.0 && h > 0.0 {
+                let corners = [(x, y), (x + w, y), (x + w, y + h), (x, y + h)];
+                Some(polyline(&corners, true, &transform))
+            } else {
+                None
+            }
+        }
+        "circle" | "ellipse" => {
+            let (cx, cy) = (number("cx"), number("cy"));
+            let (rx, ry) = if element.name == "circle" {
+                (number("r"), number("r"))
+            } else {
+                (number("rx"), number("ry"))
+            };
+            if rx > 0.0 && ry > 0.0 {
+                let data = format!(
+                    "M{} {}A{rx} {ry} 0 1 0 {} {}A{rx} {ry} 0 1 0 {} {}Z",
+                    cx - rx,
+                    cy,
+                    cx + rx,
+                    cy,
+                    cx - rx,
+                    cy,
+                    rx = rx,
+                    ry = ry
+                );
+                Some(path_data(&data, &transform)?)
+            } else {
+                None
+            }
+        }
+        "line" => {
+            let points = [(number("x1"), number("y1")), (number("x2"), number("y2"))];
+            Some(polyline(&points, false, &transform))
+        }
+        "polyline" | "polygon" => {
+            let coordinates = numbers(element.attribute("points").unwrap_or(""))?;
+            let points: Vec<(f32, f32)> = coordinates
+                .chunks_exact(2)
+                .map(|pair| (pair[0], pair[1]))
+                .collect();
+            if points.len() > 1 {
+                Some(polyline(&points, element.name == "polygon", &transform))
+            } else {
+                None
+            }
+        }
+        _ => None, // defs, symbol, style, metadata, text, ...
+    };
+    if let Some(path) = path {
+        shapes.push(Shape {
+            path,
+            style: context.style,
+        });
+    }
+    Ok(())
+}
+
+fn polyline(points: &[(f32, f32)], closed: bool, transform: &Affine) -> Path {
+    let mut builder = Path::builder();
+    for (i, (x, y)) in points.iter().enumerate() {
+        let p = transform.apply(point(*x, *y));
+        if i == 0 {
+            builder.move_to(p);
+        } else {
+            builder.line_to(p);
+        }
+    }
+    if closed {
+        builder.close();
+    }
+    builder.build()
+}
+
+/// Parse path data (the `d` attribute), mapping every point by `transform`
+pub fn path_data(data: &str, transform: &Affine) -> Result<Path, SvgError> {
+    let mut builder = Path::builder();
+    let mut scanner = Scanner::new(data);
+    let mut command = None;
+    let mut current = point(0.0, 0.0);
+    let mut start = point(0.0, 0.0);
+    let mut last_ctrl: Option<(char, Point)> = None; // For smooth curves
+    let map = |p: Point| transform.apply(p);
+
+    while scanner.has_more() {
+        let letter = match scanner.command() {
+            Some(letter) => letter,
+            // Repeated command, moves continue as lines
+            None => match command {
+                Some('M') => 'L',
+                Some('m') => 'l',
+                Some(letter) => letter,
+                None => return Err(SvgError::PathData(String::from("missing command"))),
+            },
+        };
+        command = Some(letter);
+        let relative = letter.is_ascii_lowercase();
+        let origin = if relative { current } else { point(0.0, 0.0) };
+        let read = |scanner: &mut Scanner| -> Result<Point, SvgError> {
+            let x = scanner.number()?;
+            let y = scanner.number()?;
+            Ok(point(origin.x + x, origin.y + y))
+        };
+        let mut ctrl = None;
+        match letter.to_ascii_uppercase() {
+            'M' => {
+                current = read(&mut scanner)?;
+                start = current;
+                builder.move_to(map(current));
+            }
+            'L' => {
+                current = read(&mut scanner)?;
+                builder.line_to(map(current));
+            }
+            'H' => {
+                let x = scanner.number()?;
+                current = point(if relative { current.x + x } else { x }, current.y);
+                builder.line_to(map(current));
+            }
+            'V' => {
+                let y = scanner.number()?;
+                current = point(current.x, if relative { current.y + y } else { y });
+                builder.line_to(map(current));
+            }
+            'C' | 'S' => {
+                let ctrl1 = if letter.to_ascii_uppercase() == 'C' {
+                    read(&mut scanner)?
+                } else {
+                    reflect(last_ctrl, 'C', current)
+                };
+                let ctrl2 = read(&mut scanner)?;
+                let to = read(&mut scanner)?;
+                builder.cubic_bezier_to(map(ctrl1), map(ctrl2), map(to));
+                ctrl = Some(('C', ctrl2));
+                current = to;
+            }
+            'Q' | 'T' => {
+                let ctrl1 = if letter.to_ascii_uppercase() == 'Q' {
+                    read(&mut scanner)?
+                } else {
+                    reflect(last_ctrl, 'Q', current)
+                };
+                let to = read(&mut scanner)?;
+                builder.quadratic_bezier_to(map(ctrl1), map(to));
+                ctrl = Some(('Q', ctrl1));
+                current = to;
+            }
+            'A' => {
+                let rx = scanner.number()?.abs();
+                let ry = scanner.number()?.abs();
+                let rotation = scanner.number()?;
+                let large_arc = scanner.flag()?;
+                let sweep = scanner.flag()?;
+                let to = read(&mut scanner)?;
+                for (ctrl1, ctrl2, end) in
+                    arc_to_cubics(current, to, rx, ry, rotation, large_arc, sweep)
+                {
+                    builder.cubic_bezier_to(map(ctrl1), map(ctrl2), map(end));
+                }
+                current = to;
+            }
+            'Z' => {
+                builder.close();
+                current = start;
+                command = None;
+            }
+            _ => return Err(SvgError::PathData(format!("unknown command '{}'", letter))),
+        }
+        last_ctrl = ctrl;
+    }
+    Ok(builder.build())
+}
+
+// First control point of a smooth curve, mirroring the last control point
+// of the previous curve of the same kind
+fn reflect(last: Option<(char, Point)>, kind: char, current: Point) -> Point {
+    match last {
+        Some((k, ctrl)) if k == kind => point(2.0 * current.x - ctrl.x, 2.0 * current.y - ctrl.y),
+        _ => current,
+    }
+}
+
+// Elliptical arc in endpoint form as cubic Bezier segments, following the
+// SVG implementation notes (F.6.5) for the center parametrization
+fn arc_to_cubics(
+    from: Point,
+    to: Point,
+    mut rx: f32,
+    mut ry: f32,
+    rotation: f32,
+    large_arc: bool,
+    sweep: bool,
+) -> Vec<(Point, Point, Point)> {
+    if from == to {
+        return Vec::new();
+    }
+    if rx == 0.0 || ry == 0.0 {
+        return vec![(from, to, to)];
+    }
+    let (sin, cos) = rotation.to_radians().sin_cos();
+    let dx = (from.x - to.x) / 2.0;
+    let dy = (from.y - to.y) / 2.0;
+    let x1 = cos * dx + sin * dy;
+    let y1 = -sin * dx + cos * dy;
+
+    // Radii too small to reach the end point are scaled up
+    let lambda = (x1 * x1) / (rx * rx) + (y1 * y1) / (ry * ry);
+    if lambda > 1.0 {
+        rx *= lambda.sqrt();
+        ry *= lambda.sqrt();
+    }
+    let numerator = rx * rx * ry * ry - rx * rx * y1 * y1 - ry * ry * x1 * x1;
+    let denominator = rx * rx * y1 * y1 + ry * ry * x1 * x1;
+    let mut factor = (numerator / denominator).max(0.0).sqrt();
+    if large_arc == sweep {
+        factor = -factor;
+    }
+    let cx1 = factor * rx * y1 / ry;
+    let cy1 = -factor * ry * x1 / rx;
+    let cx = cos * cx1 - sin * cy1 + (from.x + to.x) / 2.0;
+    let cy = sin * cx1 + cos * cy1 + (from.y + to.y) / 2.0;
+
+    let angle = |ux: f32, uy: f32| uy.atan2(ux);
+    let start = angle((x1 - cx1) / rx, (y1 - cy1) / ry);
+    let mut delta = angle((-x1 - cx1) / rx, (-y1 - cy1) / ry) - start;
+    if sweep && delta < 0.0 {
+        delta += 2.0 * PI;
+    } else if !sweep && delta > 0.0 {
+        delta -= 2.0 * PI;
+    }
+
+    // Each segment spans at most a quarter turn, allowing for rounding so
+    // half turns take two segments
+    let count = (delta.abs() / (PI / 2.0) - 1e-4).ceil().max(1.0) as usize;
+    let step = delta / count as f32;
+    let k = 4.0 / 3.0 * (step / 4.0).tan();
+    let on_ellipse = |t: f32| {
+        let (sin_t, cos_t) = t.sin_cos();
+        point(
+            cx + rx * cos * cos_t - ry * sin * sin_t,
+            cy + rx * sin * cos_t + ry * cos * sin_t,
+        )
+    };
+    let derivative = |t: f32| {
+        let (sin_t, cos_t) = t.sin_cos();
+        (
+            -rx * cos * sin_t - ry * sin * cos_t,
+            -rx * sin * sin_t + ry * cos * cos_t,
+        )
+    };
+    (0..count)
+        .map(|i| {
+            let (t1, t2) = (start + step * i as f32, start + step * (i + 1) as f32);
+            let (p1, p2) = (on_ellipse(t1), on_ellipse(t2));
+            let (d1, d2) = (derivative(t1), derivative(t2));
+            let end = if i + 1 == count { to } else { p2 };
+            (
+                point(p1.x + k * d1.0, p1.y + k * d1.1),
+                point(p2.x - k * d2.0, p2.y - k * d2.1),
+                end,
+            )
+        })
+        .collect()
+}
+
+// Reads commands, numbers and flags of path data and attribute lists
+struct Scanner<'a> {
+    source: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Scanner<'a> {
+    fn new(source: &'a str) -> Self {
+        Self {
+            source: source.as_bytes(),
+            position: 0,
+        }
+    }
+    fn skip_separators(&mut self) {
+        while self.position < self.source.len() {
+            match self.source[self.position] {
+                b' ' | b'\t' | b'\n' | b'\r' | b',' => self.position += 1,
+                _ => break,
+            }
+        }
+    }
+    fn has_more(&mut self) -> bool {
+        self.skip_separators();
+        self.position < self.source.len()
+    }
+    fn command(&mut self) -> Option<char> {
+        self.skip_separators();
+        match self.source.get(self.position) {
+            Some(c) if c.is_ascii_alphabetic() && *c != b'e' && *c != b'E' => {
+                self.position += 1;
+                Some(*c as char)
+            }
+            _ => None,
+        }
+    }
+    // Arc flags may be written without separators, e.g. `a1 1 0 01 5 5`
+    fn flag(&mut self) -> Result<bool, SvgError> {
+        self.skip_separators();
+        let flag = match self.source.get(self.position) {
+            Some(b'0') => false,
+            Some(b'1') => true,
+            _ => return Err(self.error("expected flag")),
+        };
+        self.position += 1;
+        Ok(flag)
+    }
+    fn number(&mut self) -> Result<f32, SvgError> {
+        self.skip_separators();
+        let start = self.position;
+        let mut seen_dot = false;
+        let mut seen_exponent = false;
+        if let Some(b'+') | Some(b'-') = self.source.get(self.position) {
+            self.position += 1;
+        }
+        while let Some(c) = self.source.get(self.position) {
+            match c {
+                b'0'..=b'9' => (),
+                // A second dot starts the next number, e.g. `0.5.5`
+                b'.' if !seen_dot && !seen_exponent => seen_dot = true,
+                b'e' | b'E' if !seen_exponent => {
+                    seen_exponent = true;
+                    if let Some(b'+') | Some(b'-') = self.source.get(self.position + 1) {
+                        self.position += 1;
+                    }
+                }
+                _ => break,
+            }
+            self.position += 1;
+        }
+        std::str::from_utf8(&self.source[start..self.position])
+            .ok()
+            .and_then(|number| number.parse().ok())
+            .ok_or_else(|| self.error("expected number"))
+    }
+    fn error(&self, message: &str) -> SvgError {
+        SvgError::PathData(format!("{} at offset {}", message, self.position))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::path::Bounds;
+
+    #[test]
+    fn compact_path_data() {
+        let path = path_data("M0 0h10v10H0z m20-5l5.5.5", &Affine::identity()).unwrap();
+        let bounds = Bounds::of_path(&path);
+        assert_eq!(bounds.min, point(0.0, -5.0));
+        assert_eq!(bounds.max, point(25.5, 10.0));
+    }
+
+    #[test]
+    fn use_references_glyphs() {
+        let svg = r#"<?xml version='1.0'?>
+            <svg xmlns='http://www.w3.org/2000/svg' xmlns:xlink='http://www.w3.org/1999/xlink'>
+            <defs><path id='g0-1' d='M0 0L1 0L1 1Z'/></defs>
+            <g transform='scale(2)'>
+              <use x='10' y='0' xlink:href='#g0-1'/>
+              <rect x='0' y='5' width='3' height='1'/>
+            </g>
+            </svg>"#;
+        let root = parse(svg).unwrap();
+        let paths = outlines(&root).unwrap();
+        assert_eq!(paths.len(), 2);
+        let glyph = Bounds::of_path(&paths[0]);
+        assert_eq!(glyph.min, point(20.0, 0.0));
+        assert_eq!(glyph.max, point(22.0, 2.0));
+    }
+
+    #[test]
+    fn styles_are_inherited() {
+        let svg = r#"<svg>
+            <g fill='#f00' stroke='blue' opacity='0.5'>
+              <circle cx='10' cy='10' r='5' style='stroke-width: 2px; fill: none'/>
+              <polygon points='0,0 4,0 4,4' fill-opacity='.5'/>
+            </g>
+            </svg>"#;
+        let shapes = shapes(&parse(svg).unwrap()).unwrap();
+        assert_eq!(shapes.len(), 2);
+
+        let circle = &shapes[0];
+        assert_eq!(circle.style.fill, None);
+        assert_eq!(circle.style.stroke, Some(Rgb::new(0.0, 0.0, 1.0)));
+        assert_eq!(circle.style.stroke_width, 2.0);
+        let bounds = Bounds::of_path(&circle.path);
+        assert!((bounds.min.x - 5.0).abs() < 1e-3 && (bounds.max.y - 15.0).abs() < 1e-3);
+
+        let polygon = &shapes[1];
+        assert_eq!(polygon.style.fill, Some(Rgb::new(1.0, 0.0, 0.0)));
+        assert_eq!(
+            (polygon.style.opacity, polygon.style.fill_opacity),
+            (0.5, 0.5)
+        );
+    }
+
+    #[test]
+    fn malformed_transforms_are_errors() {
+        for value in [") rotate(5", "scale(2", "translate(1 2) )(", "skew(1)"].iter() {
+            assert!(Affine::parse(value).is_err(), "{}", value);
+        }
+        let svg = "<svg><g transform=') rotate(5'><rect width='1' height='1'/></g></svg>";
+        assert!(shapes(&parse(svg).unwrap()).is_err());
+
+        let rotated = Affine::parse("translate(1 2) rotate(90)").unwrap();
+        let p = rotated.apply(point(1.0, 0.0));
+        assert!((p.x - 1.0).abs() < 1e-5 && (p.y - 3.0).abs() < 1e-5);
+    }
+}
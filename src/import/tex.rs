@@ -0,0 +1,232 @@
+use super::svg::{self, SvgError};
+use crate::path::map_points;
+
+use nannou::lyon::math::point;
+use nannou::lyon::path::Path;
+
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::process::Command;
+
+const DEFAULT_PREAMBLE: &str = "\\documentclass[preview]{standalone}
+\\usepackage[english]{babel}
+\\usepackage{amsmath}
+\\usepackage{amssymb}";
+
+/// Failure to typeset LaTeX
+#[derive(Debug)]
+pub enum TexError {
+    Io(io::Error),
+    /// `latex` or `dvisvgm` failed, with the end of its output
+    Toolchain {
+        program: String,
+        log: String,
+    },
+    Svg(SvgError),
+}
+
+impl fmt::Display for TexError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TexError::Io(e) => write!(f, "{}", e),
+            TexError::Toolchain { program, log } => write!(f, "{} failed:\n{}", program, log),
+            TexError::Svg(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for TexError {}
+
+impl From<io::Error> for TexError {
+    fn from(e: io::Error) -> Self {
+        TexError::Io(e)
+    }
+}
+
+impl From<SvgError> for TexError {
+    fn from(e: SvgError) -> Self {
+        TexError::Svg(e)
+    }
+}
+
+// 64-bit FNV-1a hash. Names of cached files must stay the same across runs
+// and Rust versions, which the hasher of the standard library doesn't promise.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+/// Typesets math with a local `latex` and `dvisvgm` toolchain.
+///
+/// Documents are compiled to DVI, converted to SVG with glyphs as paths, and
+/// kept in `cache_dir` under the hash of their source, so each formula is
+/// only compiled once across runs.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TexRenderer {
+    latex: String,
+    dvisvgm: String,
+    preamble: String,
+    cache_dir: PathBuf,
+}
+
+impl Default for TexRenderer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TexRenderer {
+    pub fn new() -> Self {
+        Self {
+            latex: String::from("latex"),
+            dvisvgm: String::from("dvisvgm"),
+            preamble: String::from(DEFAULT_PREAMBLE),
+            cache_dir: std::env::temp_dir().join("manim_rs").join("tex"),
+        }
+    }
+    pub fn latex(mut self, program: &str) -> Self {
+        self.latex = String::from(program);
+        self
+    }
+    pub fn dvisvgm(mut self, program: &str) -> Self {
+        self.dvisvgm = String::from(program);
+        self
+    }
+    /// Everything before `\begin{document}`, e.g. to load more packages
+    pub fn preamble(mut self, preamble: &str) -> Self {
+        self.preamble = String::from(preamble);
+        self
+    }
+    pub fn cache_dir<P: Into<PathBuf>>(mut self, dir: P) -> Self {
+        self.cache_dir = dir.into();
+        self
+    }
+    /// Document typesetting `tex` in display math mode
+    pub fn document(&self, tex: &str) -> String {
+        format!(
+            "{}\n\\begin{{document}}\n\\begin{{align*}}\n{}\n\\end{{align*}}\n\\end{{document}}\n",
+            self.preamble, tex
+        )
+    }
+    /// Path of the cached SVG of `document`
+    pub fn cached(&self, document: &str) -> PathBuf {
+        self.cache_dir
+            .join(format!("{:016x}.svg", fnv1a(document.as_bytes())))
+    }
+    /// SVG of `tex` typeset as math, compiled unless cached
+    pub fn svg(&self, tex: &str) -> Result<String, TexError> {
+        let document = self.document(tex);
+        let svg = self.cached(&document);
+        if let Ok(source) = fs::read_to_string(&svg) {
+            return Ok(source);
+        }
+        fs::create_dir_all(&self.cache_dir)?;
+        let tex_file = svg.with_extension("tex");
+        let dvi = svg.with_extension("dvi");
+        fs::write(&tex_file, &document)?;
+
+        let result = self
+            .run(
+                &self.latex,
+                &[
+                    "-interaction=nonstopmode",
+                    "-halt-on-error",
+                    &file_name(&tex_file),
+                ],
+            )
+            .and_then(|_| {
+                self.run(
+                    &self.dvisvgm,
+                    &[
+                        "--no-fonts",
+                        "--exact",
+                        "-o",
+                        &file_name(&svg),
+                        &file_name(&dvi),
+                    ],
+                )
+            });
+        // Keep only the SVG, or the log if compiling failed
+        for extension in &["tex", "dvi", "aux"] {
+            let _ = fs::remove_file(svg.with_extension(extension));
+        }
+        result?;
+        let _ = fs::remove_file(svg.with_extension("log"));
+        Ok(fs::read_to_string(&svg)?)
+    }
+    /// Outlines of the glyphs and rules of `tex` in order of the document,
+    /// with y pointing up
+    pub fn glyphs(&self, tex: &str) -> Result<Vec<Path>, TexError> {
+        let root = svg::parse(&self.svg(tex)?)?;
+        let flip = |p: nannou::lyon::math::Point| point(p.x, -p.y);
+        Ok(svg::outlines(&root)?
+            .iter()
+            .map(|path| map_points(path, flip))
+            .collect())
+    }
+    fn run(&self, program: &str, args: &[&str]) -> Result<(), TexError> {
+        let output = Command::new(program)
+            .args(args)
+            .current_dir(&self.cache_dir)
+            .output()
+            .map_err(|e| io::Error::new(e.kind(), format!("Failed to run {}: {}", program, e)))?;
+        if output.status.success() {
+            return Ok(());
+        }
+        let log = String::from_utf8_lossy(&output.stdout);
+        let lines: Vec<&str> = log.lines().collect();
+        let tail = lines[lines.len().saturating_sub(20)..].join("\n");
+        Err(TexError::Toolchain {
+            program: String::from(program),
+            log: tail,
+        })
+    }
+}
+
+fn file_name(path: &std::path::Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::path::Bounds;
+
+    #[test]
+    fn cached_svg_skips_toolchain() {
+        let dir = std::env::temp_dir().join(format!("manim_rs_tex_test_{}", std::process::id()));
+        let renderer = TexRenderer::new()
+            .latex("missing-latex")
+            .dvisvgm("missing-dvisvgm")
+            .cache_dir(&dir);
+        let svg = "<svg><path d='M0 0L4 0L4 2Z'/></svg>";
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(renderer.cached(&renderer.document("x")), svg).unwrap();
+
+        let glyphs = renderer.glyphs("x").unwrap();
+        assert_eq!(glyphs.len(), 1);
+        // Flipped to y up
+        assert_eq!(Bounds::of_path(&glyphs[0]).min, point(0.0, -2.0));
+        assert!(renderer.glyphs("y").is_err());
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn cache_names_are_stable() {
+        assert_eq!(fnv1a(b""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(fnv1a(b"a"), 0xaf63_dc4c_8601_ec8c);
+        assert_eq!(fnv1a(b"foobar"), 0x8594_4171_f739_67e8);
+
+        let renderer = TexRenderer::new().cache_dir("tex");
+        assert_eq!(
+            renderer.cached("foobar"),
+            PathBuf::from("tex").join("85944171f73967e8.svg")
+        );
+    }
+}
@@ -4,73 +4,157 @@ mod animation;
 mod app;
 mod appearance;
 mod arena;
+mod cli;
 mod consts;
 mod draw;
 mod ease;
 mod geom;
+mod import;
 mod mobject;
+mod morph;
 mod object;
 mod path;
+mod playback;
+mod render;
 mod scene;
 mod walk;
 
 use animation::{Actionable, TargetAction, UserCommand};
+use cli::{Format, Mode};
 use consts::*;
 use geom::point_at;
 // use nannou::draw::primitive::rect;
 use arena::AddObject;
-use scene::{Construct, Scene};
+use scene::{Construct, Registry, Scene};
 
 // use nannou::geom::Point2;
 use nannou::rand::random_range;
 
+/// Scenes selectable with `--scene NAME`. The first one is the default.
+fn registry() -> Registry {
+    Registry::new().add("Main", Main).add("Shapes", Shapes)
+}
+
 fn main() {
-    app::run();
+    let options = match cli::parse(std::env::args().skip(1)) {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("{}\n\n{}", e, cli::USAGE);
+            std::process::exit(1);
+        }
+    };
+    if options.help {
+        println!("{}", cli::USAGE);
+        return;
+    }
+
+    let registry = registry();
+    if options.mode == Mode::List {
+        for name in registry.names() {
+            println!("{}", name);
+        }
+        return;
+    }
+
+    let settings = options.settings(app::default_settings());
+    let name = options
+        .scene
+        .as_deref()
+        .or_else(|| registry.default_name())
+        .unwrap_or_default();
+    let mut scene = match registry.build(name, settings.window()) {
+        Some(scene) => scene,
+        None => {
+            eprintln!(
+                "Unknown scene '{}'. Available: {}",
+                name,
+                registry.names().join(", ")
+            );
+            std::process::exit(1);
+        }
+    };
+
+    match options.mode {
+        Mode::Render => {
+            let out = &options.out;
+            let result = match options.format() {
+                Format::Png => app::render_png(&mut scene, &settings, out),
+                Format::Video => app::render_video(&mut scene, &settings, out),
+                Format::Svg => app::render_svg(&mut scene, &settings, out),
+            };
+            match result {
+                Ok(frames) => println!("Rendered {} frames into {}", frames, out.display()),
+                Err(e) => {
+                    eprintln!("Failed to render: {}", e);
+                    std::process::exit(1);
+                }
+            }
+        }
+        _ => app::preview(scene, settings),
+    }
 }
 
-impl Construct for Scene {
-    fn construct(&mut self) {
+struct Main;
+struct Shapes;
+
+impl Construct for Main {
+    fn construct(&self, scene: &mut Scene) {
         let mut edge: Vec<TargetAction> = Vec::new();
         let mut create: Vec<TargetAction> = Vec::new();
         for _ in 0..600 {
             let x = random_range(-320.0, 320.0);
             let y = random_range(-240.0, 240.0);
 
-            let c = self.circle();
-            self.act(c.move_to(point_at(x, y)));
-            self.act(c.set_radius(random_range(0.1, 20.0)));
+            let c = scene.circle();
+            scene.act(c.move_to(point_at(x, y)));
+            scene.act(c.set_radius(random_range(0.1, 20.0)));
             create.push(c.show_creation());
             edge.push(c.to_edge(DOWN));
             edge.push(c.scale_by(0.3));
             // edge.push(c.set_width(0.3));
         }
 
-        self.play_many(create);
-        self.play_many(edge).rate_func(BOUNCE_OUT);
+        scene.play_many(create);
+        scene.play_many(edge).rate_func(BOUNCE_OUT);
 
-        // let c3 = self.circle();
-        // let r3 = self.rectangle();
-        // self.act(c3.move_to(point_at(200.0, -100.0)));
-        // self.act(r3.move_to(point_at(200.0, 100.0)));
-        // self.play(c3.show_creation());
-        // self.play(r3.show_creation());
+        // let c3 = scene.circle();
+        // let r3 = scene.rectangle();
+        // scene.act(c3.move_to(point_at(200.0, -100.0)));
+        // scene.act(r3.move_to(point_at(200.0, 100.0)));
+        // scene.play(c3.show_creation());
+        // scene.play(r3.show_creation());
 
-        // self.play(r3.set_height(100.0)).rate_func(QUAD);
-        // self.play(c3.set_radius(50.0)).rate_func(BOUNCE_OUT);
-        // self.play(r3.rotate_by(360.0 * 3.0)).rate_func(QUINT);
+        // scene.play(r3.set_height(100.0)).rate_func(QUAD);
+        // scene.play(c3.set_radius(50.0)).rate_func(BOUNCE_OUT);
+        // scene.play(r3.rotate_by(360.0 * 3.0)).rate_func(QUINT);
 
-        let t = self.text("Hello!");
-        // let r = self.rectangle();
+        let t = scene.text("Hello!");
+        // let r = scene.rectangle();
 
-        self.act(t.move_to(point_at(-100.0, 100.0)));
-        self.play(t.show_creation()).run_time(1.0);
-        // self.play(t.fade_in()).run_time(1.0).rate_func(QUINT);
-        self.play(t.scale_by(2.0)).rate_func(QUAD);
-        self.play(t.rotate_by(360.0 * 3.0)).rate_func(QUINT);
-        // self.play(t.set_width(100.0)).rate_func(QUAD);
+        scene.act(t.move_to(point_at(-100.0, 100.0)));
+        scene.play(t.show_creation()).run_time(1.0);
+        // scene.play(t.fade_in()).run_time(1.0).rate_func(QUINT);
+        scene.play(t.scale_by(2.0)).rate_func(QUAD);
+        scene.play(t.rotate_by(360.0 * 3.0)).rate_func(QUINT);
+        // scene.play(t.set_width(100.0)).rate_func(QUAD);
 
-        // let cut_times = self.commands.time_stamps();
-        // dbg!(&self.commands.run_times());
+        // let cut_times = scene.commands.time_stamps();
+        // dbg!(&scene.commands.run_times());
         // dbg!(&cut_times);
     }
 }
+
+impl Construct for Shapes {
+    fn construct(&self, scene: &mut Scene) {
+        let c = scene.circle();
+        let r = scene.rectangle();
+        scene.act(c.move_to(point_at(200.0, -100.0)));
+        scene.act(r.move_to(point_at(200.0, 100.0)));
+        scene.play(c.show_creation());
+        scene.play(r.show_creation());
+
+        scene.play(r.set_height(100.0)).rate_func(QUAD);
+        scene.play(c.set_radius(50.0)).rate_func(BOUNCE_OUT);
+        scene.play(r.rotate_by(360.0 * 3.0)).rate_func(QUINT);
+    }
+}
@@ -0,0 +1,267 @@
+//! Shape morphing between outlines of two objects, used by `Action::Transform`.
+//!
+//! Both outlines are flattened into polygons, which are paired by place and
+//! size, padded to the same number of polygons and resampled to the same
+//! number of points, so that any shape can be interpolated point by point
+//! into any other.
+use crate::animation::Interpolate;
+use crate::appearance::{GetColor, Paint};
+use crate::consts::*;
+use crate::draw::{draw_outline, Backend, PathStyle, Transform};
+use crate::object::Object;
+use crate::path::{polygon_path, polygons, resample, signed_area, GetPath};
+
+use nannou::lyon::math::{Point, Vector};
+
+use std::cmp::Ordering;
+
+/// Outline and colors of an object, in its local coordinates
+#[derive(Debug, Clone, PartialEq)]
+pub struct Shape {
+    polygons: Vec<Vec<Point>>,
+    color: Paint,
+    stroke_color: Paint,
+    stroke_weight: f32,
+}
+
+impl Shape {
+    pub fn of(object: &Object) -> Self {
+        Self {
+            polygons: polygons(&object.path(), DEFAULT_FLATTEN_TOLERANCE),
+            color: object.fill_paint(),
+            stroke_color: object.stroke_paint(),
+            stroke_weight: object.stroke_weight(),
+        }
+    }
+    pub fn polygons(&self) -> &[Vec<Point>] {
+        &self.polygons
+    }
+    pub fn draw<B: Backend>(&self, backend: &mut B, transform: &Transform, alpha: f32) {
+        let path = polygon_path(&self.polygons);
+        let style = PathStyle {
+            fill: self.color.clone(),
+            stroke: self.stroke_color.clone(),
+            weight: self.stroke_weight,
+            alpha,
+            filled: true,
+        };
+        draw_outline(backend, &path, &path, transform, style);
+    }
+}
+
+impl Interpolate for Shape {
+    fn interp_mut(&mut self, other: &Self, progress: f32) {
+        *self = self.interp(other, progress);
+    }
+    // Shapes must have matching points, see `Morph::new`
+    fn interp(&self, other: &Self, progress: f32) -> Self {
+        let polygons = self
+            .polygons
+            .iter()
+            .zip(other.polygons.iter())
+            .map(|(a, b)| {
+                a.iter()
+                    .zip(b.iter())
+                    .map(|(p, q)| p.lerp(*q, progress))
+                    .collect()
+            })
+            .collect();
+        Self {
+            polygons,
+            color: self.color.interp(&other.color, progress),
+            stroke_color: self.stroke_color.interp(&other.stroke_color, progress),
+            stroke_weight: self.stroke_weight.interp(&other.stroke_weight, progress),
+        }
+    }
+}
+
+/// Pair of shapes with matching polygons and points
+#[derive(Debug, Clone, PartialEq)]
+pub struct Morph {
+    from: Shape,
+    to: Shape,
+}
+
+impl Morph {
+    pub fn new(from: &Object, to: &Object) -> Self {
+        Self::between(Shape::of(from), Shape::of(to))
+    }
+    fn between(mut from: Shape, mut to: Shape) -> Self {
+        let (a, b) = pair(&from.polygons, &to.polygons);
+        from.polygons = a;
+        to.polygons = b;
+
+        for (a, b) in from.polygons.iter_mut().zip(to.polygons.iter_mut()) {
+            *a = resample(a, MORPH_SAMPLES);
+            *b = resample(b, MORPH_SAMPLES);
+            align(a, b);
+        }
+        Self { from, to }
+    }
+    pub fn at(&self, progress: f32) -> Shape {
+        self.from.interp(&self.to, progress)
+    }
+}
+
+fn centroid(polygon: &[Point]) -> Point {
+    if polygon.is_empty() {
+        return Point::new(0.0, 0.0);
+    }
+    let sum = polygon
+        .iter()
+        .fold(Vector::new(0.0, 0.0), |sum, p| sum + p.to_vector());
+    (sum / polygon.len() as f32).to_point()
+}
+
+// Pair polygons closest in place and size, so that separate pieces of an
+// outline morph into the matching pieces of the other, whatever order they
+// were drawn in. Polygons left without a pair grow out of, or shrink into,
+// their own center.
+fn pair(from: &[Vec<Point>], to: &[Vec<Point>]) -> (Vec<Vec<Point>>, Vec<Vec<Point>>) {
+    let place = |polygon: &Vec<Point>| (centroid(polygon), signed_area(polygon).abs().sqrt());
+    let (a, b): (Vec<_>, Vec<_>) = (
+        from.iter().map(place).collect(),
+        to.iter().map(place).collect(),
+    );
+    let mut costs = Vec::new();
+    for (i, (ca, sa)) in a.iter().enumerate() {
+        for (j, (cb, sb)) in b.iter().enumerate() {
+            costs.push(((*cb - *ca).square_length() + (sb - sa).powi(2), i, j));
+        }
+    }
+    costs.sort_by(|x, y| x.0.partial_cmp(&y.0).unwrap_or(Ordering::Equal));
+
+    let mut partner: Vec<Option<usize>> = vec![None; from.len()];
+    let mut taken = vec![false; to.len()];
+    for (_, i, j) in costs {
+        if partner[i].is_none() && !taken[j] {
+            partner[i] = Some(j);
+            taken[j] = true;
+        }
+    }
+
+    let mut pairs: (Vec<Vec<Point>>, Vec<Vec<Point>>) = (Vec::new(), Vec::new());
+    for (i, polygon) in from.iter().enumerate() {
+        pairs.0.push(polygon.clone());
+        pairs.1.push(match partner[i] {
+            Some(j) => to[j].clone(),
+            None => vec![a[i].0],
+        });
+    }
+    for (j, polygon) in to.iter().enumerate().filter(|(j, _)| !taken[*j]) {
+        pairs.0.push(vec![b[j].0]);
+        pairs.1.push(polygon.clone());
+    }
+    pairs
+}
+
+// Match winding and starting point of `to` with `from`, so the outline
+// doesn't flip or twist while morphing
+fn align(from: &[Point], to: &mut [Point]) {
+    if to.is_empty() || from.len() != to.len() {
+        return;
+    }
+    if signed_area(from) * signed_area(to) < 0.0 {
+        to.reverse();
+    }
+    let n = to.len();
+    let cost = |offset: usize| -> f32 {
+        from.iter()
+            .enumerate()
+            .map(|(i, p)| (to[(i + offset) % n] - *p).square_length())
+            .sum()
+    };
+    let costs: Vec<f32> = (0..n).map(cost).collect();
+    let best = (0..n)
+        .min_by(|a, b| costs[*a].partial_cmp(&costs[*b]).unwrap_or(Ordering::Equal))
+        .unwrap_or(0);
+    to.rotate_left(best);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geom::SetDimension;
+    use crate::object::circle::circle;
+    use crate::object::rectangle::rectangle;
+
+    #[test]
+    fn circle_into_rectangle() {
+        let c = circle().inner;
+        let mut r = rectangle();
+        r.set_width(100.0);
+        let r = r.inner;
+
+        let morph = Morph::new(&c, &r);
+        let start = morph.at(0.0);
+        let end = morph.at(1.0);
+        assert_eq!(start.polygons().len(), 1);
+        assert_eq!(start.polygons()[0].len(), MORPH_SAMPLES);
+        assert_eq!(end.polygons()[0].len(), MORPH_SAMPLES);
+
+        // Every point ends up on the rectangle outline
+        for p in end.polygons()[0].iter() {
+            let on_side = (p.x.abs() - 50.0).abs() < 1e-3 || (p.y.abs() - 15.0).abs() < 1e-3;
+            assert!(on_side, "{:?} is not on the outline", p);
+        }
+        assert_eq!(end.color, r.fill_paint());
+    }
+
+    #[test]
+    fn pieces_pair_by_place() {
+        let square = |x: f32, size: f32| {
+            vec![
+                Point::new(x, 0.0),
+                Point::new(x + size, 0.0),
+                Point::new(x + size, size),
+                Point::new(x, size),
+            ]
+        };
+        let shape = |polygons| Shape {
+            polygons,
+            color: Paint::Solid(DEFAULT_FILL_COLOR),
+            stroke_color: Paint::Solid(DEFAULT_STROKE_COLOR),
+            stroke_weight: 0.0,
+        };
+        // Same pieces drawn in the other order, plus one more
+        let from = shape(vec![square(0.0, 10.0), square(100.0, 20.0)]);
+        let to = shape(vec![
+            square(200.0, 5.0),
+            square(100.0, 20.0),
+            square(0.0, 10.0),
+        ]);
+
+        let morph = Morph::between(from, to);
+        let end = morph.at(1.0);
+        assert_eq!(end.polygons().len(), 3);
+        let near = |polygon: &[Point], p: Point| (centroid(polygon) - p).length() < 1e-3;
+        assert!(near(&end.polygons()[0], Point::new(5.0, 5.0)));
+        assert!(near(&end.polygons()[1], Point::new(110.0, 10.0)));
+
+        // Extra piece grows out of its own center
+        let start = morph.at(0.0);
+        assert!(start.polygons()[2]
+            .iter()
+            .all(|p| (*p - Point::new(202.5, 2.5)).length() < 1e-4));
+    }
+
+    #[test]
+    fn align_skips_nan() {
+        let from = vec![
+            Point::new(0.0, 0.0),
+            Point::new(1.0, 0.0),
+            Point::new(1.0, 1.0),
+        ];
+        let mut to = vec![
+            Point::new(1.0, 1.0),
+            Point::new(std::f32::NAN, 0.0),
+            Point::new(1.0, 0.0),
+        ];
+        align(&from, &mut to);
+        assert_eq!(to.len(), 3);
+
+        let mut rotated = vec![from[1], from[2], from[0]];
+        align(&from, &mut rotated);
+        assert_eq!(rotated, from);
+    }
+}
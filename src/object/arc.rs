@@ -0,0 +1,275 @@
+use crate::animation::PathCompletion;
+use crate::appearance::{GetColor, GetOpacity, Opacity, Paint, SetColor, SetOpacity};
+use crate::arena::Object;
+use crate::consts::*;
+use crate::draw::{draw_outline, Backend, Draw, PathStyle, Transform};
+use crate::geom;
+use crate::geom::{dimension, Dimension, GetDimension, SetDimension};
+use crate::geom::{GetOrientation, GetPosition, SetOrientation, SetPosition};
+use crate::path::{GetPath, MeasureCache};
+
+use nannou;
+use nannou::color::Rgb;
+use nannou::lyon::math as lyon;
+use nannou::lyon::math::{point, Angle, Vector};
+use nannou::lyon::path::Path;
+
+use std::f32::consts::PI;
+
+/// Part of an ellipse outline between two angles, like manim's `Arc`.
+/// With an inner radius it is filled, as a `Sector` when the inner radius is
+/// zero, or as an `Annulus` when it goes all the way around.
+/// Angles are in degrees, counter-clockwise from the x axis.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Arc {
+    start_angle: f32,
+    angle: f32,
+    inner: Option<f32>, // Inner radius relative to outer one, open arc if none
+    dimension: Dimension,
+    position: geom::Point,
+    orientation: f32,
+    path_completion: f32,
+    color: Paint,
+    stroke_color: Paint,
+    stroke_weight: f32,
+    opacity: Opacity,
+    measured: MeasureCache,
+}
+
+impl Arc {
+    fn new(angle: f32, inner: Option<f32>) -> Self {
+        let size = DEFAULT_ARC_RADIUS * 2.0;
+        Arc {
+            start_angle: 0.0,
+            angle,
+            inner: inner.map(|ratio| ratio.max(0.0).min(1.0)),
+            dimension: dimension(size, size),
+            position: geom::point(),
+            orientation: 0.0,
+            path_completion: 1.0,
+            color: Paint::Solid(DEFAULT_FILL_COLOR),
+            stroke_color: Paint::Solid(DEFAULT_STROKE_COLOR),
+            stroke_weight: DEFAULT_STROKE_WEIGHT,
+            opacity: Opacity::new(false),
+            measured: MeasureCache::default(),
+        }
+    }
+    pub fn start_angle(&self) -> f32 {
+        self.start_angle
+    }
+    pub fn set_start_angle(&mut self, angle: f32) {
+        self.measured.clear();
+        self.start_angle = angle;
+    }
+    pub fn angle(&self) -> f32 {
+        self.angle
+    }
+    pub fn set_angle(&mut self, angle: f32) {
+        self.measured.clear();
+        self.angle = angle;
+    }
+    pub fn inner_ratio(&self) -> Option<f32> {
+        self.inner
+    }
+    pub fn is_filled(&self) -> bool {
+        self.inner.is_some()
+    }
+}
+
+impl GetPath for Arc {
+    fn path(&self) -> Path {
+        let outer = Vector::new(self.width() / 2.0, self.height() / 2.0);
+        let start = self.start_angle.to_radians();
+        let sweep = self.angle.to_radians();
+        let center: lyon::Point = point(0.0, 0.0);
+        let x_rotation = Angle::radians(0.0);
+        let at = |radii: Vector, angle: f32| point(radii.x * angle.cos(), radii.y * angle.sin());
+
+        let mut builder = Path::builder();
+        match self.inner {
+            None => {
+                builder.move_to(at(outer, start));
+                builder.arc(center, outer, Angle::radians(sweep), x_rotation);
+            }
+            Some(ratio) if ratio <= 0.0 => {
+                builder.move_to(center);
+                builder.line_to(at(outer, start));
+                builder.arc(center, outer, Angle::radians(sweep), x_rotation);
+                builder.close();
+            }
+            Some(ratio) => {
+                let inner = outer * ratio;
+                builder.move_to(at(outer, start));
+                builder.arc(center, outer, Angle::radians(sweep), x_rotation);
+                // Full ring is made of two separate outlines, without a seam
+                if sweep.abs() >= PI * 2.0 {
+                    builder.close();
+                    builder.move_to(at(inner, start + sweep));
+                } else {
+                    builder.line_to(at(inner, start + sweep));
+                }
+                builder.arc(center, inner, Angle::radians(-sweep), x_rotation);
+                builder.close();
+            }
+        }
+        builder.build()
+    }
+}
+
+impl Draw for Arc {
+    fn draw<B: Backend>(&self, backend: &mut B) {
+        if self.is_visible() {
+            let measured = self.measured.get(|| self.path());
+            let path = measured.upto(self.path_completion);
+            let transform = Transform::new(self.position, self.orientation);
+            // Open arc is only a stroke
+            let style = PathStyle::of(self).filled(self.is_filled());
+            draw_outline(backend, measured.path(), &path, &transform, style);
+        }
+    }
+}
+
+impl PathCompletion for Arc {
+    fn completion(&self) -> f32 {
+        self.path_completion
+    }
+    fn set_completion(&mut self, completion: f32) {
+        self.path_completion = completion.max(0.0).min(1.0);
+    }
+}
+
+impl SetPosition for Arc {
+    fn position_mut(&mut self) -> &mut geom::Point {
+        SetPosition::position_mut(&mut self.position)
+    }
+}
+
+impl GetPosition for Arc {
+    fn position(&self) -> geom::Point {
+        GetPosition::position(&self.position)
+    }
+}
+
+impl GetOrientation for Arc {
+    fn orientation(&self) -> f32 {
+        self.orientation
+    }
+}
+
+impl SetOrientation for Arc {
+    fn orientation_mut(&mut self) -> &mut f32 {
+        &mut self.orientation
+    }
+}
+
+impl GetDimension for Arc {
+    fn dimension(&self) -> &Dimension {
+        GetDimension::dimension(&self.dimension)
+    }
+}
+
+impl SetDimension for Arc {
+    fn dimension_mut(&mut self) -> &mut Dimension {
+        self.measured.clear();
+        SetDimension::dimension_mut(&mut self.dimension)
+    }
+}
+
+impl GetColor for Arc {
+    fn color(&self) -> Rgb {
+        self.color.color()
+    }
+    fn stroke_color(&self) -> Rgb {
+        self.stroke_color.color()
+    }
+    fn stroke_weight(&self) -> f32 {
+        self.stroke_weight
+    }
+    fn fill_paint(&self) -> Paint {
+        self.color.clone()
+    }
+    fn stroke_paint(&self) -> Paint {
+        self.stroke_color.clone()
+    }
+}
+
+impl SetColor for Arc {
+    fn set_fill_paint(&mut self, paint: Paint) {
+        self.color = paint;
+    }
+    fn set_stroke_paint(&mut self, paint: Paint) {
+        self.stroke_color = paint;
+    }
+    fn set_stroke_weight(&mut self, weight: f32) {
+        self.stroke_weight = weight.max(0.0);
+    }
+}
+
+impl GetOpacity for Arc {
+    fn opacity(&self) -> f32 {
+        GetOpacity::opacity(&self.opacity)
+    }
+    fn is_visible(&self) -> bool {
+        GetOpacity::is_visible(&self.opacity)
+    }
+}
+
+impl SetOpacity for Arc {
+    fn opacity_mut(&mut self) -> &mut Opacity {
+        SetOpacity::opacity_mut(&mut self.opacity)
+    }
+}
+
+/// Open arc spanning `angle` degrees
+pub fn arc(angle: f32) -> Object {
+    Object::new(Arc::new(angle, None).into())
+}
+
+/// Filled slice of a disc spanning `angle` degrees
+pub fn sector(angle: f32) -> Object {
+    Object::new(Arc::new(angle, Some(0.0)).into())
+}
+
+/// Ring with inner radius of `inner_ratio` times the outer radius
+pub fn annulus(inner_ratio: f32) -> Object {
+    Object::new(Arc::new(360.0, Some(inner_ratio)).into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::path::{polygons, Bounds};
+
+    fn near(a: lyon::Point, x: f32, y: f32) -> bool {
+        (a.x - x).abs() < 0.05 && (a.y - y).abs() < 0.05
+    }
+
+    #[test]
+    fn open_and_filled_outlines() {
+        let half = Arc::new(180.0, None);
+        assert!(!half.is_filled());
+        let bounds = Bounds::of_outline(&half.path());
+        assert!(near(bounds.min, -20.0, 0.0) && near(bounds.max, 20.0, 20.0));
+
+        // Sector outline runs through its center
+        let quarter = Arc::new(90.0, Some(0.0));
+        let bounds = Bounds::of_outline(&quarter.path());
+        assert!(near(bounds.min, 0.0, 0.0) && near(bounds.max, 20.0, 20.0));
+    }
+
+    #[test]
+    fn annulus_resizes_both_rings() {
+        let mut ring = Arc::new(360.0, Some(0.5));
+        assert_eq!(ring.inner_ratio(), Some(0.5));
+        assert_eq!(Arc::new(360.0, Some(2.0)).inner_ratio(), Some(1.0));
+
+        ring.set_width(80.0);
+        let rings = polygons(&ring.path(), DEFAULT_FLATTEN_TOLERANCE);
+        assert_eq!(rings.len(), 2);
+        let (outer, inner) = (Bounds::of_outline(&ring.path()), &rings[1]);
+        assert!(near(outer.min, -40.0, -20.0) && near(outer.max, 40.0, 20.0));
+        let inner_x = inner.iter().fold(0.0f32, |x, p| x.max(p.x.abs()));
+        let inner_y = inner.iter().fold(0.0f32, |y, p| y.max(p.y.abs()));
+        assert!((inner_x - 20.0).abs() < 0.05 && (inner_y - 10.0).abs() < 0.05);
+    }
+}
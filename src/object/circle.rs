@@ -1,29 +1,32 @@
 use crate::animation::PathCompletion;
-use crate::appearance::{GetOpacity, Opacity, SetOpacity};
+use crate::appearance::{GetColor, GetOpacity, Opacity, Paint, SetColor, SetOpacity};
 use crate::arena::Object;
 use crate::consts::*;
-use crate::draw::Draw;
+use crate::draw::{draw_outline, Backend, Draw, PathStyle, Transform};
 use crate::geom;
 use crate::geom::{dimension, Dimension, GetDimension, SetDimension};
-use crate::geom::{point_at, GetPosition, SetPosition};
-use crate::path::GetPartial;
+use crate::geom::{point_at, GetOrientation, GetPosition, SetOrientation, SetPosition};
+use crate::path::{GetPath, MeasureCache};
 
 use nannou;
-use nannou::color::{Rgb, Rgba};
+use nannou::color::Rgb;
 use nannou::lyon::math as lyon;
 use nannou::lyon::math::{point, Angle, Vector};
 use nannou::lyon::path::Path;
 
 use std::f32::consts::PI;
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Circle {
     dimension: Dimension,
     position: geom::Point,
+    orientation: f32,
     path_completion: f32,
-    color: Rgb,
-    stroke_color: Rgb,
+    color: Paint,
+    stroke_color: Paint,
+    stroke_weight: f32,
     opacity: Opacity,
+    measured: MeasureCache,
 }
 
 impl Circle {
@@ -31,10 +34,13 @@ impl Circle {
         Circle {
             dimension: dimension(12.0, 12.0),
             position: point_at(0.0, 0.0),
+            orientation: 0.0,
             path_completion: 1.0,
-            color: DEFAULT_FILL_COLOR,
-            stroke_color: DEFAULT_STROKE_COLOR,
+            color: Paint::Solid(DEFAULT_FILL_COLOR),
+            stroke_color: Paint::Solid(DEFAULT_STROKE_COLOR),
+            stroke_weight: DEFAULT_STROKE_WEIGHT,
             opacity: Opacity::new(false),
+            measured: MeasureCache::default(),
         }
     }
 
@@ -48,43 +54,36 @@ impl Circle {
     }
 }
 
+impl GetPath for Circle {
+    fn path(&self) -> Path {
+        let mut builder = Path::builder();
+        let sweep_angle = Angle::radians(PI * 2.0);
+        let x_rotation = Angle::radians(0.0);
+        let center: lyon::Point = point(0.0, 0.0);
+        let start = point(self.radius(), 0.0);
+        let radii = Vector::new(self.radius(), self.radius());
+
+        builder.move_to(start);
+        builder.arc(center, radii, sweep_angle, x_rotation);
+        builder.close();
+
+        builder.build()
+    }
+}
+
 impl Draw for Circle {
-    fn draw(&self, draw: nannou::Draw) {
+    fn draw<B: Backend>(&self, backend: &mut B) {
         if self.is_visible() {
-            let mut builder = Path::builder();
-            let sweep_angle = Angle::radians(PI * 2.0);
-            let x_rotation = Angle::radians(0.0);
-            let center: lyon::Point = point(0.0, 0.0);
-            let start = point(self.radius(), 0.0);
-            let radii = Vector::new(self.radius(), self.radius());
-
-            builder.move_to(start);
-            builder.arc(center, radii, sweep_angle, x_rotation);
-            builder.close();
-
-            let path = builder.build();
-            let path = path.upto(self.path_completion, DEFAULT_FLATTEN_TOLERANCE);
-
-            let color = Rgba {
-                color: self.color,
-                alpha: self.alpha(),
-            };
-            let stroke_color = Rgba {
-                color: self.stroke_color,
-                alpha: self.alpha(),
-            };
-
-            draw.path()
-                .fill()
-                .x_y(self.position.x, self.position.y)
-                .color(color)
-                .events(&path);
-            draw.path()
-                .stroke()
-                .x_y(self.position.x, self.position.y)
-                .color(stroke_color)
-                .stroke_weight(DEFAULT_STROKE_WEIGHT)
-                .events(&path);
+            let measured = self.measured.get(|| self.path());
+            let path = measured.upto(self.path_completion);
+            let transform = Transform::new(self.position, self.orientation);
+            draw_outline(
+                backend,
+                measured.path(),
+                &path,
+                &transform,
+                PathStyle::of(self),
+            );
         }
     }
 }
@@ -110,6 +109,18 @@ impl GetPosition for Circle {
     }
 }
 
+impl GetOrientation for Circle {
+    fn orientation(&self) -> f32 {
+        self.orientation
+    }
+}
+
+impl SetOrientation for Circle {
+    fn orientation_mut(&mut self) -> &mut f32 {
+        &mut self.orientation
+    }
+}
+
 impl GetDimension for Circle {
     fn dimension(&self) -> &Dimension {
         GetDimension::dimension(&self.dimension)
@@ -118,6 +129,7 @@ impl GetDimension for Circle {
 
 impl SetDimension for Circle {
     fn dimension_mut(&mut self) -> &mut Dimension {
+        self.measured.clear();
         SetDimension::dimension_mut(&mut self.dimension)
     }
     fn set_width(&mut self, width: f32) {
@@ -128,6 +140,36 @@ impl SetDimension for Circle {
     }
 }
 
+impl GetColor for Circle {
+    fn color(&self) -> Rgb {
+        self.color.color()
+    }
+    fn stroke_color(&self) -> Rgb {
+        self.stroke_color.color()
+    }
+    fn stroke_weight(&self) -> f32 {
+        self.stroke_weight
+    }
+    fn fill_paint(&self) -> Paint {
+        self.color.clone()
+    }
+    fn stroke_paint(&self) -> Paint {
+        self.stroke_color.clone()
+    }
+}
+
+impl SetColor for Circle {
+    fn set_fill_paint(&mut self, paint: Paint) {
+        self.color = paint;
+    }
+    fn set_stroke_paint(&mut self, paint: Paint) {
+        self.stroke_color = paint;
+    }
+    fn set_stroke_weight(&mut self, weight: f32) {
+        self.stroke_weight = weight.max(0.0);
+    }
+}
+
 impl GetOpacity for Circle {
     fn opacity(&self) -> f32 {
         GetOpacity::opacity(&self.opacity)
@@ -146,3 +188,45 @@ impl SetOpacity for Circle {
 pub fn circle() -> Object {
     Object::new(Circle::new().into())
 }
+
+/// Small circle marking a point
+pub fn dot() -> Object {
+    let mut dot = Circle::new();
+    dot.set_radius(DEFAULT_DOT_RADIUS);
+    dot.color = Paint::Solid(DEFAULT_STROKE_COLOR);
+    Object::new(dot.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::object::Object as Inner;
+    use crate::path::Bounds;
+
+    #[test]
+    fn dot_stays_round() {
+        let mut small = match dot().inner {
+            Inner::Circle(circle) => circle,
+            _ => unreachable!(),
+        };
+        assert_eq!(small.radius(), DEFAULT_DOT_RADIUS);
+        assert_eq!(small.fill_paint(), Paint::Solid(DEFAULT_STROKE_COLOR));
+
+        small.set_width(10.0);
+        assert_eq!(
+            (small.width(), small.height(), small.radius()),
+            (10.0, 10.0, 5.0)
+        );
+        let bounds = Bounds::of_outline(&small.path());
+        assert!((bounds.max.x - 5.0).abs() < 0.05 && (bounds.max.y - 5.0).abs() < 0.05);
+    }
+
+    #[test]
+    fn resizing_measures_again() {
+        let mut circle = Circle::new();
+        let before = circle.measured.get(|| circle.path()).length();
+        circle.set_radius(12.0);
+        let after = circle.measured.get(|| circle.path()).length();
+        assert!((after / before - 2.0).abs() < 0.01);
+    }
+}
@@ -0,0 +1,194 @@
+use crate::animation::PathCompletion;
+use crate::appearance::{GetColor, GetOpacity, Opacity, Paint, SetColor, SetOpacity};
+use crate::arena::Object;
+use crate::consts::*;
+use crate::draw::{draw_outline, Backend, Draw, PathStyle, Transform};
+use crate::geom;
+use crate::geom::{dimension, Dimension, GetDimension, SetDimension};
+use crate::geom::{GetOrientation, GetPosition, SetOrientation, SetPosition};
+use crate::path::{GetPath, MeasureCache};
+
+use nannou;
+use nannou::color::Rgb;
+use nannou::lyon::math as lyon;
+use nannou::lyon::math::{point, Angle, Vector};
+use nannou::lyon::path::Path;
+
+use std::f32::consts::PI;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Ellipse {
+    dimension: Dimension,
+    position: geom::Point,
+    orientation: f32,
+    path_completion: f32,
+    color: Paint,
+    stroke_color: Paint,
+    stroke_weight: f32,
+    opacity: Opacity,
+    measured: MeasureCache,
+}
+
+impl Ellipse {
+    fn new() -> Self {
+        Ellipse {
+            dimension: dimension(24.0, 12.0),
+            position: geom::point(),
+            orientation: 0.0,
+            path_completion: 1.0,
+            color: Paint::Solid(DEFAULT_FILL_COLOR),
+            stroke_color: Paint::Solid(DEFAULT_STROKE_COLOR),
+            stroke_weight: DEFAULT_STROKE_WEIGHT,
+            opacity: Opacity::new(false),
+            measured: MeasureCache::default(),
+        }
+    }
+}
+
+impl GetPath for Ellipse {
+    fn path(&self) -> Path {
+        let mut builder = Path::builder();
+        let sweep_angle = Angle::radians(PI * 2.0);
+        let x_rotation = Angle::radians(0.0);
+        let center: lyon::Point = point(0.0, 0.0);
+        let start = point(self.width() / 2.0, 0.0);
+        let radii = Vector::new(self.width() / 2.0, self.height() / 2.0);
+
+        builder.move_to(start);
+        builder.arc(center, radii, sweep_angle, x_rotation);
+        builder.close();
+
+        builder.build()
+    }
+}
+
+impl Draw for Ellipse {
+    fn draw<B: Backend>(&self, backend: &mut B) {
+        if self.is_visible() {
+            let measured = self.measured.get(|| self.path());
+            let path = measured.upto(self.path_completion);
+            let transform = Transform::new(self.position, self.orientation);
+            draw_outline(
+                backend,
+                measured.path(),
+                &path,
+                &transform,
+                PathStyle::of(self),
+            );
+        }
+    }
+}
+
+impl PathCompletion for Ellipse {
+    fn completion(&self) -> f32 {
+        self.path_completion
+    }
+    fn set_completion(&mut self, completion: f32) {
+        self.path_completion = completion.max(0.0).min(1.0);
+    }
+}
+
+impl SetPosition for Ellipse {
+    fn position_mut(&mut self) -> &mut geom::Point {
+        SetPosition::position_mut(&mut self.position)
+    }
+}
+
+impl GetPosition for Ellipse {
+    fn position(&self) -> geom::Point {
+        GetPosition::position(&self.position)
+    }
+}
+
+impl GetOrientation for Ellipse {
+    fn orientation(&self) -> f32 {
+        self.orientation
+    }
+}
+
+impl SetOrientation for Ellipse {
+    fn orientation_mut(&mut self) -> &mut f32 {
+        &mut self.orientation
+    }
+}
+
+impl GetDimension for Ellipse {
+    fn dimension(&self) -> &Dimension {
+        GetDimension::dimension(&self.dimension)
+    }
+}
+
+impl SetDimension for Ellipse {
+    fn dimension_mut(&mut self) -> &mut Dimension {
+        self.measured.clear();
+        SetDimension::dimension_mut(&mut self.dimension)
+    }
+}
+
+impl GetColor for Ellipse {
+    fn color(&self) -> Rgb {
+        self.color.color()
+    }
+    fn stroke_color(&self) -> Rgb {
+        self.stroke_color.color()
+    }
+    fn stroke_weight(&self) -> f32 {
+        self.stroke_weight
+    }
+    fn fill_paint(&self) -> Paint {
+        self.color.clone()
+    }
+    fn stroke_paint(&self) -> Paint {
+        self.stroke_color.clone()
+    }
+}
+
+impl SetColor for Ellipse {
+    fn set_fill_paint(&mut self, paint: Paint) {
+        self.color = paint;
+    }
+    fn set_stroke_paint(&mut self, paint: Paint) {
+        self.stroke_color = paint;
+    }
+    fn set_stroke_weight(&mut self, weight: f32) {
+        self.stroke_weight = weight.max(0.0);
+    }
+}
+
+impl GetOpacity for Ellipse {
+    fn opacity(&self) -> f32 {
+        GetOpacity::opacity(&self.opacity)
+    }
+    fn is_visible(&self) -> bool {
+        GetOpacity::is_visible(&self.opacity)
+    }
+}
+
+impl SetOpacity for Ellipse {
+    fn opacity_mut(&mut self) -> &mut Opacity {
+        SetOpacity::opacity_mut(&mut self.opacity)
+    }
+}
+
+pub fn ellipse() -> Object {
+    Object::new(Ellipse::new().into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::path::Bounds;
+
+    #[test]
+    fn outline_fits_size() {
+        let mut ellipse = Ellipse::new();
+        let bounds = Bounds::of_outline(&ellipse.path());
+        assert!((bounds.max.x - 12.0).abs() < 0.05 && (bounds.max.y - 6.0).abs() < 0.05);
+
+        // Height changes alone, unlike a circle
+        ellipse.set_height(30.0);
+        let bounds = Bounds::of_outline(&ellipse.path());
+        assert_eq!(ellipse.width(), 24.0);
+        assert!((bounds.max.x - 12.0).abs() < 0.05 && (bounds.min.y + 15.0).abs() < 0.05);
+    }
+}
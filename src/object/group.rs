@@ -0,0 +1,167 @@
+use crate::animation::PathCompletion;
+use crate::appearance::{GetColor, GetOpacity, Opacity, Paint, SetColor, SetOpacity};
+use crate::arena::Object;
+use crate::consts::*;
+use crate::draw::{Backend, Draw, Transform};
+use crate::geom;
+use crate::geom::{dimension, Dimension, GetDimension, SetDimension};
+use crate::geom::{GetOrientation, GetPosition, SetOrientation, SetPosition};
+use crate::path::GetPath;
+
+use nannou::color::Rgb;
+use nannou::lyon::path::Path;
+
+/// Container of other objects, like manim's `VGroup`.
+/// Position, orientation, scale and opacity of a group apply to all of its
+/// children, whose own properties are relative to the group. Children are
+/// linked through `Node`, and drawn by `Scene`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Group {
+    position: geom::Point,
+    orientation: f32,
+    dimension: Dimension,
+    base: Dimension, // Size of children at scale 1
+    opacity: Opacity,
+}
+
+impl Group {
+    fn new() -> Self {
+        Group {
+            position: geom::point(),
+            orientation: 0.0,
+            dimension: dimension(0.0, 0.0),
+            base: dimension(0.0, 0.0),
+            opacity: Opacity::new(true),
+        }
+    }
+    /// Uniform scale applied to children
+    pub fn scale(&self) -> f32 {
+        if self.base.width() > 0.0 {
+            self.width() / self.base.width()
+        } else if self.base.height() > 0.0 {
+            self.height() / self.base.height()
+        } else {
+            1.0
+        }
+    }
+    pub fn set_scale(&mut self, scale: f32) {
+        self.dimension = dimension(self.base.width() * scale, self.base.height() * scale);
+    }
+    /// Update size of children at scale 1, keeping current scale
+    pub fn set_base(&mut self, base: Dimension) {
+        let scale = self.scale();
+        self.base = base;
+        self.set_scale(scale);
+    }
+    pub fn transform(&self) -> Transform {
+        Transform::new(self.position, self.orientation).with_scale(self.scale())
+    }
+}
+
+impl Draw for Group {
+    // Children are drawn by `Scene`, nothing to draw by itself
+    fn draw<B: Backend>(&self, _backend: &mut B) {}
+}
+
+impl GetPath for Group {
+    fn path(&self) -> Path {
+        Path::new()
+    }
+}
+
+impl PathCompletion for Group {
+    fn completion(&self) -> f32 {
+        1.0
+    }
+    fn set_completion(&mut self, _completion: f32) {}
+}
+
+impl SetPosition for Group {
+    fn position_mut(&mut self) -> &mut geom::Point {
+        SetPosition::position_mut(&mut self.position)
+    }
+}
+
+impl GetPosition for Group {
+    fn position(&self) -> geom::Point {
+        GetPosition::position(&self.position)
+    }
+}
+
+impl GetOrientation for Group {
+    fn orientation(&self) -> f32 {
+        self.orientation
+    }
+}
+
+impl SetOrientation for Group {
+    fn orientation_mut(&mut self) -> &mut f32 {
+        &mut self.orientation
+    }
+}
+
+impl GetDimension for Group {
+    fn dimension(&self) -> &Dimension {
+        GetDimension::dimension(&self.dimension)
+    }
+}
+
+// Groups only scale uniformly, so any change of size keeps the aspect ratio
+impl SetDimension for Group {
+    fn dimension_mut(&mut self) -> &mut Dimension {
+        SetDimension::dimension_mut(&mut self.dimension)
+    }
+    fn set_width(&mut self, width: f32) {
+        if self.base.width() > 0.0 {
+            self.set_scale(width / self.base.width());
+        }
+    }
+    fn set_height(&mut self, height: f32) {
+        if self.base.height() > 0.0 {
+            self.set_scale(height / self.base.height());
+        }
+    }
+    fn set_size(&mut self, size: Dimension) {
+        if self.base.width() > 0.0 {
+            self.set_width(size.width());
+        } else {
+            self.set_height(size.height());
+        }
+    }
+}
+
+impl GetColor for Group {
+    fn color(&self) -> Rgb {
+        DEFAULT_FILL_COLOR
+    }
+    fn stroke_color(&self) -> Rgb {
+        DEFAULT_STROKE_COLOR
+    }
+}
+
+// Groups have no color of their own, color actions on a group are passed
+// on to the objects in it instead
+impl SetColor for Group {
+    fn set_fill_paint(&mut self, _paint: Paint) {}
+    fn set_stroke_paint(&mut self, _paint: Paint) {}
+    fn set_stroke_weight(&mut self, _weight: f32) {}
+}
+
+impl GetOpacity for Group {
+    fn opacity(&self) -> f32 {
+        GetOpacity::opacity(&self.opacity)
+    }
+    fn is_visible(&self) -> bool {
+        GetOpacity::is_visible(&self.opacity)
+    }
+}
+
+impl SetOpacity for Group {
+    fn opacity_mut(&mut self) -> &mut Opacity {
+        SetOpacity::opacity_mut(&mut self.opacity)
+    }
+}
+
+pub fn group() -> Object {
+    Object::new(Group::new().into())
+}
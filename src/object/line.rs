@@ -0,0 +1,357 @@
+use crate::animation::PathCompletion;
+use crate::appearance::{GetColor, GetOpacity, Opacity, Paint, SetColor, SetOpacity};
+use crate::arena::Object;
+use crate::consts::*;
+use crate::draw::{Backend, Draw, PathStyle, Transform};
+use crate::geom;
+use crate::geom::{dimension, Dimension, GetDimension, SetDimension};
+use crate::geom::{GetOrientation, GetPosition, SetOrientation, SetPosition};
+use crate::path::{polygon_path, GetPath};
+
+use crate::object::polygon::normalize;
+use nannou;
+use nannou::color::Rgb;
+
+use nannou::lyon::math as lyon;
+use nannou::lyon::path::builder::PathBuilder;
+use nannou::lyon::path::Path;
+
+use std::f32::consts::PI;
+
+/// Shape at the end of a line
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum TipShape {
+    Triangle,
+    Stealth, // Triangle with notched back
+    Round,
+    Square,
+}
+
+/// Tip of an arrow. Its length is taken from the line, so the line
+/// still spans from start to end including the tip.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct ArrowTip {
+    pub shape: TipShape,
+    pub length: f32,
+    pub width: f32,
+}
+
+impl ArrowTip {
+    pub fn new(shape: TipShape) -> Self {
+        Self {
+            shape,
+            length: DEFAULT_TIP_LENGTH,
+            width: DEFAULT_TIP_LENGTH,
+        }
+    }
+    pub fn triangle() -> Self {
+        Self::new(TipShape::Triangle)
+    }
+    pub fn stealth() -> Self {
+        Self::new(TipShape::Stealth)
+    }
+    pub fn round() -> Self {
+        Self::new(TipShape::Round)
+    }
+    pub fn square() -> Self {
+        Self::new(TipShape::Square)
+    }
+    pub fn length(mut self, length: f32) -> Self {
+        self.length = length;
+        self
+    }
+    pub fn width(mut self, width: f32) -> Self {
+        self.width = width;
+        self
+    }
+    /// Outline of tip pointing at `point` along `direction` (unit vector)
+    pub fn outline(&self, point: lyon::Point, direction: lyon::Vector) -> Vec<lyon::Point> {
+        let back = point - direction * self.length;
+        let side = lyon::vector(-direction.y, direction.x) * (self.width / 2.0);
+        match self.shape {
+            TipShape::Triangle => vec![point, back + side, back - side],
+            TipShape::Stealth => {
+                let notch = back + direction * (self.length * 0.3);
+                vec![point, back + side, notch, back - side]
+            }
+            TipShape::Square => {
+                let half = direction * (self.length / 2.0);
+                let side = side * (self.length / self.width.max(std::f32::EPSILON));
+                let center = point - half;
+                vec![
+                    center + half + side,
+                    center - half + side,
+                    center - half - side,
+                    center + half - side,
+                ]
+            }
+            TipShape::Round => {
+                let radius = self.length / 2.0;
+                let center = point - direction * radius;
+                (0..DEFAULT_TIP_SEGMENTS)
+                    .map(|i| {
+                        let angle = 2.0 * PI * i as f32 / DEFAULT_TIP_SEGMENTS as f32;
+                        center + lyon::vector(angle.cos(), angle.sin()) * radius
+                    })
+                    .collect()
+            }
+        }
+    }
+}
+
+/// Straight line, which becomes an arrow with tips at either end
+#[derive(Debug, Clone, PartialEq)]
+pub struct Line {
+    start: lyon::Point, // Within box of unit size around origin
+    end: lyon::Point,
+    start_tip: Option<ArrowTip>,
+    end_tip: Option<ArrowTip>,
+    dimension: Dimension,
+    position: geom::Point,
+    orientation: f32,
+    path_completion: f32,
+    color: Paint,
+    stroke_color: Paint,
+    stroke_weight: f32,
+    opacity: Opacity,
+}
+
+impl Line {
+    fn new(start: geom::Point, end: geom::Point) -> Self {
+        let mut line = Line {
+            start: lyon::point(0.0, 0.0),
+            end: lyon::point(0.0, 0.0),
+            start_tip: None,
+            end_tip: None,
+            dimension: dimension(0.0, 0.0),
+            position: geom::point(),
+            orientation: 0.0,
+            path_completion: 1.0,
+            color: Paint::Solid(DEFAULT_STROKE_COLOR), // Tips are filled in same color as line
+            stroke_color: Paint::Solid(DEFAULT_STROKE_COLOR),
+            stroke_weight: DEFAULT_STROKE_WEIGHT,
+            opacity: Opacity::new(false),
+        };
+        line.set_start_and_end(start, end);
+        line
+    }
+    /// Start point in local coordinates of the object
+    pub fn start(&self) -> lyon::Point {
+        lyon::point(self.start.x * self.width(), self.start.y * self.height())
+    }
+    /// End point in local coordinates of the object
+    pub fn end(&self) -> lyon::Point {
+        lyon::point(self.end.x * self.width(), self.end.y * self.height())
+    }
+    /// Start and end points in coordinates of the scene (or parent group)
+    pub fn start_and_end(&self) -> (geom::Point, geom::Point) {
+        let transform = Transform::new(self.position, self.orientation);
+        (
+            transform.apply(self.start()).into(),
+            transform.apply(self.end()).into(),
+        )
+    }
+    /// Place line between `start` and `end`, resetting its orientation
+    pub fn set_start_and_end(&mut self, start: geom::Point, end: geom::Point) {
+        let (position, dimension, points) = normalize(&[start, end]);
+        self.position = position;
+        self.dimension = dimension;
+        self.orientation = 0.0;
+        self.start = points[0];
+        self.end = points[1];
+    }
+    pub fn tips(&self) -> (Option<ArrowTip>, Option<ArrowTip>) {
+        (self.start_tip, self.end_tip)
+    }
+    pub fn set_tips(&mut self, start: Option<ArrowTip>, end: Option<ArrowTip>) {
+        self.start_tip = start;
+        self.end_tip = end;
+    }
+    // Shaft grows from start with completion, tips stay at its ends
+    fn parts(&self, completion: f32) -> (Path, Vec<Vec<lyon::Point>>) {
+        let (start, end) = (self.start(), self.end());
+        let length = (end - start).length();
+        let direction = if length > 0.0 {
+            (end - start) / length
+        } else {
+            lyon::vector(1.0, 0.0)
+        };
+        let tip_length = |tip: Option<ArrowTip>| tip.map_or(0.0, |tip| tip.length.min(length));
+
+        let from = tip_length(self.start_tip);
+        let to = (length * completion - tip_length(self.end_tip)).max(from);
+        let shaft_start = start + direction * from;
+        let shaft_end = start + direction * to;
+        let mut builder = Path::builder();
+        builder.move_to(shaft_start);
+        builder.line_to(shaft_end);
+
+        let mut tips = Vec::new();
+        if length > 0.0 && completion > 0.0 {
+            if let Some(tip) = self.start_tip {
+                tips.push(tip.outline(start, -direction));
+            }
+            if let Some(tip) = self.end_tip {
+                tips.push(tip.outline(start + direction * (length * completion), direction));
+            }
+        }
+        (builder.build(), tips)
+    }
+}
+
+impl GetPath for Line {
+    fn path(&self) -> Path {
+        let (shaft, tips) = self.parts(1.0);
+        let mut builder = Path::builder();
+        for e in shaft.iter() {
+            builder.path_event(e);
+        }
+        for tip in tips.iter() {
+            builder.move_to(tip[0]);
+            for p in tip.iter().skip(1) {
+                builder.line_to(*p);
+            }
+            builder.close();
+        }
+        builder.build()
+    }
+}
+
+impl Draw for Line {
+    fn draw<B: Backend>(&self, backend: &mut B) {
+        if self.is_visible() {
+            let outline = self.path();
+            let (shaft, tips) = self.parts(self.path_completion);
+
+            let style = PathStyle::of(self).fit(&outline);
+            let transform = Transform::new(self.position, self.orientation);
+
+            style.stroke(backend, &shaft, &transform);
+            for tip in tips.iter() {
+                style.fill(backend, &polygon_path(&[tip.clone()]), &transform);
+            }
+        }
+    }
+}
+
+impl PathCompletion for Line {
+    fn completion(&self) -> f32 {
+        self.path_completion
+    }
+    fn set_completion(&mut self, completion: f32) {
+        self.path_completion = completion.max(0.0).min(1.0);
+    }
+}
+
+impl SetPosition for Line {
+    fn position_mut(&mut self) -> &mut geom::Point {
+        SetPosition::position_mut(&mut self.position)
+    }
+}
+
+impl GetPosition for Line {
+    fn position(&self) -> geom::Point {
+        GetPosition::position(&self.position)
+    }
+}
+
+impl GetOrientation for Line {
+    fn orientation(&self) -> f32 {
+        self.orientation
+    }
+}
+
+impl SetOrientation for Line {
+    fn orientation_mut(&mut self) -> &mut f32 {
+        &mut self.orientation
+    }
+}
+
+impl GetDimension for Line {
+    fn dimension(&self) -> &Dimension {
+        GetDimension::dimension(&self.dimension)
+    }
+}
+
+impl SetDimension for Line {
+    fn dimension_mut(&mut self) -> &mut Dimension {
+        SetDimension::dimension_mut(&mut self.dimension)
+    }
+}
+
+impl GetColor for Line {
+    fn color(&self) -> Rgb {
+        self.color.color()
+    }
+    fn stroke_color(&self) -> Rgb {
+        self.stroke_color.color()
+    }
+    fn stroke_weight(&self) -> f32 {
+        self.stroke_weight
+    }
+    fn fill_paint(&self) -> Paint {
+        self.color.clone()
+    }
+    fn stroke_paint(&self) -> Paint {
+        self.stroke_color.clone()
+    }
+}
+
+impl SetColor for Line {
+    fn set_fill_paint(&mut self, paint: Paint) {
+        self.color = paint;
+    }
+    fn set_stroke_paint(&mut self, paint: Paint) {
+        self.stroke_color = paint;
+    }
+    fn set_stroke_weight(&mut self, weight: f32) {
+        self.stroke_weight = weight.max(0.0);
+    }
+}
+
+impl GetOpacity for Line {
+    fn opacity(&self) -> f32 {
+        GetOpacity::opacity(&self.opacity)
+    }
+    fn is_visible(&self) -> bool {
+        GetOpacity::is_visible(&self.opacity)
+    }
+}
+
+impl SetOpacity for Line {
+    fn opacity_mut(&mut self) -> &mut Opacity {
+        SetOpacity::opacity_mut(&mut self.opacity)
+    }
+}
+
+pub fn line(start: geom::Point, end: geom::Point) -> Object {
+    Object::new(Line::new(start, end).into())
+}
+
+pub fn arrow(start: geom::Point, end: geom::Point) -> Object {
+    let mut line = Line::new(start, end);
+    line.set_tips(None, Some(ArrowTip::triangle()));
+    Object::new(line.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geom::point_at;
+
+    #[test]
+    fn arrow_tip_at_end() {
+        let mut line = Line::new(point_at(-50.0, 0.0), point_at(50.0, 0.0));
+        line.set_tips(None, Some(ArrowTip::triangle().length(10.0)));
+        assert_eq!(line.position(), point_at(0.0, 0.0));
+
+        let (_, tips) = line.parts(1.0);
+        assert_eq!(tips[0][0], lyon::point(50.0, 0.0));
+        // Half drawn arrow carries its tip along
+        let (_, tips) = line.parts(0.5);
+        assert_eq!(tips[0][0], lyon::point(0.0, 0.0));
+
+        line.set_width(200.0);
+        assert_eq!(line.start_and_end().1, point_at(100.0, 0.0));
+    }
+}
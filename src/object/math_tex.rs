@@ -0,0 +1,361 @@
+use crate::animation::PathCompletion;
+use crate::appearance::{GetColor, GetOpacity, Opacity, Paint, SetColor, SetOpacity};
+use crate::arena::Object;
+use crate::consts::*;
+use crate::draw::{Backend, Draw, PathStyle, Transform};
+use crate::geom;
+use crate::geom::{dimension, Dimension, GetDimension, SetDimension};
+use crate::geom::{point_at, GetOrientation, GetPosition, SetOrientation, SetPosition};
+use crate::import::{TexError, TexRenderer};
+use crate::object::text::lagged_progress;
+use crate::path::{concat, map_points, partials, same_path, Bounds, GetPath, Measured};
+
+use nannou;
+use nannou::color::Rgb;
+use nannou::lyon::math::point;
+use nannou::lyon::path::Path;
+
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::ops::Range;
+use std::sync::Arc;
+
+/// Typeset math, like manim's `MathTex`.
+///
+/// Formula is given in parts, which are joined into one LaTeX string. Each
+/// part is a submobject covering a range of glyphs, so that it can be colored
+/// on its own. Glyphs are kept relative to the size of the formula, so
+/// resizing stretches them like `VPath`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MathTex {
+    parts: Vec<String>,
+    ranges: Vec<Range<usize>>, // Glyphs of every part
+    glyphs: Glyphs,            // Within box of unit size around origin
+    dimension: Dimension,
+    position: geom::Point,
+    orientation: f32,
+    path_completion: f32,
+    write_progress: Option<f32>, // Progress of `Write` animation, if running
+    color: Paint,
+    glyph_colors: BTreeMap<usize, Paint>,
+    stroke_color: Paint,
+    stroke_weight: f32,
+    opacity: Opacity,
+    scaled: ScaledCache,
+}
+
+// Glyphs at the current size, measured so that any part of them is drawn
+// without flattening them again, and the outline they make up
+#[derive(Debug)]
+struct Scaled {
+    outline: Path,
+    glyphs: Vec<Measured>,
+}
+
+// Scaled glyphs, made when first needed and cleared when the formula is
+// resized. Formulas are equal whether or not they were scaled yet.
+#[derive(Debug, Clone, Default)]
+struct ScaledCache(RefCell<Option<Arc<Scaled>>>);
+
+impl PartialEq for ScaledCache {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+// Outlines compared by their events, as paths can't be compared
+#[derive(Debug, Clone)]
+struct Glyphs(Vec<Path>);
+
+impl PartialEq for Glyphs {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.len() == other.0.len()
+            && self
+                .0
+                .iter()
+                .zip(other.0.iter())
+                .all(|(a, b)| same_path(a, b))
+    }
+}
+
+impl MathTex {
+    /// Typeset `parts` with `renderer`. With more than one part, every part
+    /// is also typeset alone to find how many glyphs it has.
+    pub fn new(parts: &[&str], renderer: &TexRenderer) -> Result<Self, TexError> {
+        let glyphs = renderer.glyphs(&parts.join(" "))?;
+        let mut ranges = Vec::new();
+        if parts.len() > 1 {
+            let mut start = 0;
+            for part in parts.iter() {
+                let end = (start + renderer.glyphs(part)?.len()).min(glyphs.len());
+                ranges.push(start..end);
+                start = end;
+            }
+        } else {
+            ranges.push(0..glyphs.len());
+        }
+
+        let bounds = Bounds::of_path(&concat(&glyphs));
+        let (width, height) = if bounds.is_empty() {
+            (0.0, 0.0)
+        } else {
+            (bounds.max.x - bounds.min.x, bounds.max.y - bounds.min.y)
+        };
+        let center = bounds.center();
+        let fit = |value: f32, extent: f32| if extent > 0.0 { value / extent } else { 0.0 };
+        let glyphs = glyphs
+            .iter()
+            .map(|glyph| {
+                map_points(glyph, |p| {
+                    point(fit(p.x - center.x, width), fit(p.y - center.y, height))
+                })
+            })
+            .collect();
+
+        Ok(MathTex {
+            parts: parts.iter().map(|part| String::from(*part)).collect(),
+            ranges,
+            glyphs: Glyphs(glyphs),
+            dimension: dimension(width * DEFAULT_TEX_SCALE, height * DEFAULT_TEX_SCALE),
+            position: point_at(0.0, 0.0),
+            orientation: 0.0,
+            path_completion: 1.0,
+            write_progress: None,
+            color: Paint::Solid(DEFAULT_STROKE_COLOR),
+            glyph_colors: BTreeMap::new(),
+            stroke_color: Paint::Solid(DEFAULT_STROKE_COLOR),
+            stroke_weight: DEFAULT_TEXT_STROKE_WEIGHT,
+            opacity: Opacity::new(false),
+            scaled: ScaledCache::default(),
+        })
+    }
+    pub fn parts(&self) -> &[String] {
+        &self.parts
+    }
+    pub fn glyph_count(&self) -> usize {
+        self.glyphs.0.len()
+    }
+    /// Glyphs of part `index`
+    pub fn part_glyphs(&self, index: usize) -> Range<usize> {
+        self.ranges.get(index).cloned().unwrap_or(0..0)
+    }
+    /// Indices of parts containing `pattern`, like manim's `set_color_by_tex`
+    pub fn part_indices(&self, pattern: &str) -> Vec<usize> {
+        self.parts
+            .iter()
+            .enumerate()
+            .filter(|(_, part)| !pattern.is_empty() && part.contains(pattern))
+            .map(|(i, _)| i)
+            .collect()
+    }
+    /// Fill of glyph `index`, which is the fill of the formula unless set apart
+    pub fn glyph_color(&self, index: usize) -> Paint {
+        self.glyph_colors
+            .get(&index)
+            .cloned()
+            .unwrap_or_else(|| self.color.clone())
+    }
+    pub fn set_glyph_color(&mut self, index: usize, paint: Paint) {
+        if index < self.glyph_count() {
+            self.glyph_colors.insert(index, paint);
+        }
+    }
+    pub fn clear_glyph_colors(&mut self) {
+        self.glyph_colors.clear();
+    }
+    pub fn write_progress(&self) -> Option<f32> {
+        self.write_progress
+    }
+    /// Set progress of `Write` animation, or `None` to draw normally
+    pub fn set_write_progress(&mut self, progress: Option<f32>) {
+        self.write_progress = progress.map(|p| p.max(0.0).min(1.0));
+    }
+    // Glyphs at the current size
+    fn scaled_glyphs(&self) -> Vec<Path> {
+        let (width, height) = (self.width(), self.height());
+        self.glyphs
+            .0
+            .iter()
+            .map(|glyph| map_points(glyph, |p| point(p.x * width, p.y * height)))
+            .collect()
+    }
+    fn scaled(&self) -> Arc<Scaled> {
+        self.scaled
+            .0
+            .borrow_mut()
+            .get_or_insert_with(|| {
+                let glyphs = self.scaled_glyphs();
+                Arc::new(Scaled {
+                    outline: concat(&glyphs),
+                    glyphs: glyphs
+                        .iter()
+                        .map(|glyph| Measured::new(glyph, DEFAULT_FLATTEN_TOLERANCE))
+                        .collect(),
+                })
+            })
+            .clone()
+    }
+    // Fill of a glyph set apart is fitted to the glyph alone, others share
+    // the fill of the whole formula
+    fn glyph_paint(&self, index: usize, glyph: &Path, color: &Paint) -> Paint {
+        match self.glyph_colors.get(&index) {
+            Some(paint) => paint.fit(glyph),
+            None => color.clone(),
+        }
+    }
+    // Glyphs are written one after another, like `Text`
+    fn draw_writing<B: Backend>(
+        &self,
+        backend: &mut B,
+        glyphs: &[Measured],
+        transform: &Transform,
+        style: &PathStyle,
+        progress: f32,
+    ) {
+        let count = glyphs.len();
+        for (i, glyph) in glyphs.iter().enumerate() {
+            let p = lagged_progress(progress, i, count);
+            if p <= 0.0 {
+                break;
+            }
+            let stroke = glyph.upto((p * 2.0).min(1.0));
+            let fill_alpha = (p * 2.0 - 1.0).max(0.0) * style.alpha;
+
+            if fill_alpha > 0.0 {
+                let paint = self.glyph_paint(i, glyph.path(), &style.fill);
+                backend.fill_paint(glyph.path(), transform, &paint, fill_alpha);
+            }
+            style.stroke(backend, &stroke, transform);
+        }
+    }
+}
+
+impl GetPath for MathTex {
+    fn path(&self) -> Path {
+        self.scaled().outline.clone()
+    }
+}
+
+impl Draw for MathTex {
+    fn draw<B: Backend>(&self, backend: &mut B) {
+        if self.is_visible() {
+            let scaled = self.scaled();
+            let transform = Transform::new(self.position, self.orientation);
+            let style = PathStyle::of(self).fit(&scaled.outline);
+
+            if let Some(progress) = self.write_progress {
+                self.draw_writing(backend, &scaled.glyphs, &transform, &style, progress);
+                return;
+            }
+            // Glyphs are drawn in order, so together they make the part of
+            // the outline drawn so far
+            let partial = partials(&scaled.glyphs, self.path_completion);
+            let path = concat(&partial);
+
+            if self.glyph_colors.is_empty() {
+                style.fill(backend, &path, &transform);
+            } else {
+                for (i, (glyph, part)) in scaled.glyphs.iter().zip(partial.iter()).enumerate() {
+                    let paint = self.glyph_paint(i, glyph.path(), &style.fill);
+                    backend.fill_paint(part, &transform, &paint, style.alpha);
+                }
+            }
+            style.stroke(backend, &path, &transform);
+        }
+    }
+}
+
+impl PathCompletion for MathTex {
+    fn completion(&self) -> f32 {
+        self.path_completion
+    }
+    fn set_completion(&mut self, completion: f32) {
+        self.path_completion = completion.max(0.0).min(1.0);
+    }
+}
+
+impl SetPosition for MathTex {
+    fn position_mut(&mut self) -> &mut geom::Point {
+        SetPosition::position_mut(&mut self.position)
+    }
+}
+
+impl GetPosition for MathTex {
+    fn position(&self) -> geom::Point {
+        GetPosition::position(&self.position)
+    }
+}
+
+impl GetOrientation for MathTex {
+    fn orientation(&self) -> f32 {
+        self.orientation
+    }
+}
+
+impl SetOrientation for MathTex {
+    fn orientation_mut(&mut self) -> &mut f32 {
+        &mut self.orientation
+    }
+}
+
+impl GetDimension for MathTex {
+    fn dimension(&self) -> &Dimension {
+        GetDimension::dimension(&self.dimension)
+    }
+}
+
+impl SetDimension for MathTex {
+    fn dimension_mut(&mut self) -> &mut Dimension {
+        *self.scaled.0.get_mut() = None;
+        SetDimension::dimension_mut(&mut self.dimension)
+    }
+}
+
+impl GetColor for MathTex {
+    fn color(&self) -> Rgb {
+        self.color.color()
+    }
+    fn stroke_color(&self) -> Rgb {
+        self.stroke_color.color()
+    }
+    fn stroke_weight(&self) -> f32 {
+        self.stroke_weight
+    }
+    fn fill_paint(&self) -> Paint {
+        self.color.clone()
+    }
+    fn stroke_paint(&self) -> Paint {
+        self.stroke_color.clone()
+    }
+}
+
+impl SetColor for MathTex {
+    fn set_fill_paint(&mut self, paint: Paint) {
+        self.color = paint;
+    }
+    fn set_stroke_paint(&mut self, paint: Paint) {
+        self.stroke_color = paint;
+    }
+    fn set_stroke_weight(&mut self, weight: f32) {
+        self.stroke_weight = weight.max(0.0);
+    }
+}
+
+impl GetOpacity for MathTex {
+    fn opacity(&self) -> f32 {
+        GetOpacity::opacity(&self.opacity)
+    }
+    fn is_visible(&self) -> bool {
+        GetOpacity::is_visible(&self.opacity)
+    }
+}
+
+impl SetOpacity for MathTex {
+    fn opacity_mut(&mut self) -> &mut Opacity {
+        SetOpacity::opacity_mut(&mut self.opacity)
+    }
+}
+
+pub fn math_tex(parts: &[&str], renderer: &TexRenderer) -> Result<Object, TexError> {
+    Ok(Object::new(MathTex::new(parts, renderer)?.into()))
+}
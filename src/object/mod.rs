@@ -1,24 +1,61 @@
 use crate::animation::PathCompletion;
-use crate::appearance::{GetOpacity, Opacity, SetOpacity};
-use crate::draw::Draw;
+use crate::appearance::{GetColor, GetOpacity, Opacity, Paint, SetColor, SetOpacity};
+use crate::draw::{Backend, Draw, Transform};
 use crate::geom::{Dimension, GetDimension, SetDimension};
 use crate::geom::{GetOrientation, GetPosition, Point, SetOrientation, SetPosition};
+use crate::path::GetPath;
 
 use nannou;
+use nannou::color::Rgb;
+use nannou::lyon::path::Path;
 
+pub use self::arc::Arc;
 pub use self::circle::Circle;
+pub use self::ellipse::Ellipse;
+pub use self::group::Group;
+pub use self::line::{ArrowTip, Line, TipShape};
+pub use self::math_tex::MathTex;
+pub use self::polygon::Polygon;
 pub use self::rectangle::Rectangle;
-pub use self::text::Text;
+pub use self::text::{Alignment, Text, TextStyle};
+pub use self::value_tracker::ValueTracker;
+pub use self::vpath::{VPath, VPathBuilder};
 
+pub mod arc;
 pub mod circle;
+pub mod ellipse;
+pub mod group;
+pub mod line;
+pub mod math_tex;
+pub mod polygon;
 pub mod rectangle;
 pub mod text;
+pub mod value_tracker;
+pub mod vpath;
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum Object {
     Circle(Circle),
     Rectangle(Rectangle),
     Text(Text),
+    MathTex(MathTex),
+    Line(Line),
+    Polygon(Polygon),
+    Arc(Arc),
+    Ellipse(Ellipse),
+    Path(VPath),
+    Group(Group),
+    ValueTracker(ValueTracker),
+}
+
+impl Object {
+    /// Placement of object, which also applies to its children
+    pub fn transform(&self) -> Transform {
+        match self {
+            Object::Group(o) => o.transform(),
+            _ => Transform::new(self.position(), self.orientation()),
+        }
+    }
 }
 
 impl SetPosition for Object {
@@ -27,6 +64,14 @@ impl SetPosition for Object {
             Object::Circle(o) => SetPosition::position_mut(o),
             Object::Rectangle(o) => SetPosition::position_mut(o),
             Object::Text(o) => SetPosition::position_mut(o),
+            Object::MathTex(o) => SetPosition::position_mut(o),
+            Object::Line(o) => SetPosition::position_mut(o),
+            Object::Polygon(o) => SetPosition::position_mut(o),
+            Object::Arc(o) => SetPosition::position_mut(o),
+            Object::Ellipse(o) => SetPosition::position_mut(o),
+            Object::Path(o) => SetPosition::position_mut(o),
+            Object::Group(o) => SetPosition::position_mut(o),
+            Object::ValueTracker(o) => SetPosition::position_mut(o),
         }
     }
 }
@@ -37,6 +82,14 @@ impl GetPosition for Object {
             Object::Circle(o) => GetPosition::position(o),
             Object::Rectangle(o) => GetPosition::position(o),
             Object::Text(o) => GetPosition::position(o),
+            Object::MathTex(o) => GetPosition::position(o),
+            Object::Line(o) => GetPosition::position(o),
+            Object::Polygon(o) => GetPosition::position(o),
+            Object::Arc(o) => GetPosition::position(o),
+            Object::Ellipse(o) => GetPosition::position(o),
+            Object::Path(o) => GetPosition::position(o),
+            Object::Group(o) => GetPosition::position(o),
+            Object::ValueTracker(o) => GetPosition::position(o),
         }
     }
 }
@@ -44,9 +97,17 @@ impl GetPosition for Object {
 impl SetOrientation for Object {
     fn orientation_mut(&mut self) -> &mut f32 {
         match self {
-            Object::Circle(_) => panic!("Circle cannot rotate... yet."),
+            Object::Circle(o) => SetOrientation::orientation_mut(o),
             Object::Rectangle(o) => SetOrientation::orientation_mut(o),
             Object::Text(o) => SetOrientation::orientation_mut(o),
+            Object::MathTex(o) => SetOrientation::orientation_mut(o),
+            Object::Line(o) => SetOrientation::orientation_mut(o),
+            Object::Polygon(o) => SetOrientation::orientation_mut(o),
+            Object::Arc(o) => SetOrientation::orientation_mut(o),
+            Object::Ellipse(o) => SetOrientation::orientation_mut(o),
+            Object::Path(o) => SetOrientation::orientation_mut(o),
+            Object::Group(o) => SetOrientation::orientation_mut(o),
+            Object::ValueTracker(o) => SetOrientation::orientation_mut(o),
         }
     }
 }
@@ -54,9 +115,17 @@ impl SetOrientation for Object {
 impl GetOrientation for Object {
     fn orientation(&self) -> f32 {
         match self {
-            Object::Circle(_) => panic!("Circle does not have angle... yet."),
+            Object::Circle(o) => GetOrientation::orientation(o),
             Object::Rectangle(o) => GetOrientation::orientation(o),
             Object::Text(o) => GetOrientation::orientation(o),
+            Object::MathTex(o) => GetOrientation::orientation(o),
+            Object::Line(o) => GetOrientation::orientation(o),
+            Object::Polygon(o) => GetOrientation::orientation(o),
+            Object::Arc(o) => GetOrientation::orientation(o),
+            Object::Ellipse(o) => GetOrientation::orientation(o),
+            Object::Path(o) => GetOrientation::orientation(o),
+            Object::Group(o) => GetOrientation::orientation(o),
+            Object::ValueTracker(o) => GetOrientation::orientation(o),
         }
     }
 }
@@ -67,6 +136,14 @@ impl GetDimension for Object {
             Object::Circle(o) => GetDimension::dimension(o),
             Object::Rectangle(o) => GetDimension::dimension(o),
             Object::Text(o) => GetDimension::dimension(o),
+            Object::MathTex(o) => GetDimension::dimension(o),
+            Object::Line(o) => GetDimension::dimension(o),
+            Object::Polygon(o) => GetDimension::dimension(o),
+            Object::Arc(o) => GetDimension::dimension(o),
+            Object::Ellipse(o) => GetDimension::dimension(o),
+            Object::Path(o) => GetDimension::dimension(o),
+            Object::Group(o) => GetDimension::dimension(o),
+            Object::ValueTracker(o) => GetDimension::dimension(o),
         }
     }
 }
@@ -77,6 +154,14 @@ impl SetDimension for Object {
             Object::Circle(o) => SetDimension::dimension_mut(o),
             Object::Rectangle(o) => SetDimension::dimension_mut(o),
             Object::Text(o) => SetDimension::dimension_mut(o),
+            Object::MathTex(o) => SetDimension::dimension_mut(o),
+            Object::Line(o) => SetDimension::dimension_mut(o),
+            Object::Polygon(o) => SetDimension::dimension_mut(o),
+            Object::Arc(o) => SetDimension::dimension_mut(o),
+            Object::Ellipse(o) => SetDimension::dimension_mut(o),
+            Object::Path(o) => SetDimension::dimension_mut(o),
+            Object::Group(o) => SetDimension::dimension_mut(o),
+            Object::ValueTracker(o) => SetDimension::dimension_mut(o),
         }
     }
     fn set_height(&mut self, height: f32) {
@@ -84,6 +169,14 @@ impl SetDimension for Object {
             Object::Circle(o) => SetDimension::set_height(o, height),
             Object::Rectangle(o) => SetDimension::set_height(o, height),
             Object::Text(o) => SetDimension::set_height(o, height),
+            Object::MathTex(o) => SetDimension::set_height(o, height),
+            Object::Line(o) => SetDimension::set_height(o, height),
+            Object::Polygon(o) => SetDimension::set_height(o, height),
+            Object::Arc(o) => SetDimension::set_height(o, height),
+            Object::Ellipse(o) => SetDimension::set_height(o, height),
+            Object::Path(o) => SetDimension::set_height(o, height),
+            Object::Group(o) => SetDimension::set_height(o, height),
+            Object::ValueTracker(o) => SetDimension::set_height(o, height),
         }
     }
     fn set_width(&mut self, width: f32) {
@@ -91,6 +184,14 @@ impl SetDimension for Object {
             Object::Circle(o) => SetDimension::set_width(o, width),
             Object::Rectangle(o) => SetDimension::set_width(o, width),
             Object::Text(o) => SetDimension::set_width(o, width),
+            Object::MathTex(o) => SetDimension::set_width(o, width),
+            Object::Line(o) => SetDimension::set_width(o, width),
+            Object::Polygon(o) => SetDimension::set_width(o, width),
+            Object::Arc(o) => SetDimension::set_width(o, width),
+            Object::Ellipse(o) => SetDimension::set_width(o, width),
+            Object::Path(o) => SetDimension::set_width(o, width),
+            Object::Group(o) => SetDimension::set_width(o, width),
+            Object::ValueTracker(o) => SetDimension::set_width(o, width),
         }
     }
     fn set_size(&mut self, size: Dimension) {
@@ -98,6 +199,14 @@ impl SetDimension for Object {
             Object::Circle(o) => SetDimension::set_size(o, size),
             Object::Rectangle(o) => SetDimension::set_size(o, size),
             Object::Text(o) => SetDimension::set_size(o, size),
+            Object::MathTex(o) => SetDimension::set_size(o, size),
+            Object::Line(o) => SetDimension::set_size(o, size),
+            Object::Polygon(o) => SetDimension::set_size(o, size),
+            Object::Arc(o) => SetDimension::set_size(o, size),
+            Object::Ellipse(o) => SetDimension::set_size(o, size),
+            Object::Path(o) => SetDimension::set_size(o, size),
+            Object::Group(o) => SetDimension::set_size(o, size),
+            Object::ValueTracker(o) => SetDimension::set_size(o, size),
         }
     }
 }
@@ -108,6 +217,14 @@ impl PathCompletion for Object {
             Object::Circle(o) => o.completion(),
             Object::Rectangle(o) => o.completion(),
             Object::Text(o) => o.completion(),
+            Object::MathTex(o) => o.completion(),
+            Object::Line(o) => o.completion(),
+            Object::Polygon(o) => o.completion(),
+            Object::Arc(o) => o.completion(),
+            Object::Ellipse(o) => o.completion(),
+            Object::Path(o) => o.completion(),
+            Object::Group(o) => o.completion(),
+            Object::ValueTracker(o) => o.completion(),
         }
     }
     fn set_completion(&mut self, completion: f32) {
@@ -115,16 +232,176 @@ impl PathCompletion for Object {
             Object::Circle(o) => o.set_completion(completion),
             Object::Rectangle(o) => o.set_completion(completion),
             Object::Text(o) => o.set_completion(completion),
+            Object::MathTex(o) => o.set_completion(completion),
+            Object::Line(o) => o.set_completion(completion),
+            Object::Polygon(o) => o.set_completion(completion),
+            Object::Arc(o) => o.set_completion(completion),
+            Object::Ellipse(o) => o.set_completion(completion),
+            Object::Path(o) => o.set_completion(completion),
+            Object::Group(o) => o.set_completion(completion),
+            Object::ValueTracker(o) => o.set_completion(completion),
         }
     }
 }
 
 impl Draw for Object {
-    fn draw(&self, draw: nannou::Draw) {
+    fn draw<B: Backend>(&self, backend: &mut B) {
+        match self {
+            Object::Circle(o) => o.draw(backend),
+            Object::Rectangle(o) => o.draw(backend),
+            Object::Text(o) => o.draw(backend),
+            Object::MathTex(o) => o.draw(backend),
+            Object::Line(o) => o.draw(backend),
+            Object::Polygon(o) => o.draw(backend),
+            Object::Arc(o) => o.draw(backend),
+            Object::Ellipse(o) => o.draw(backend),
+            Object::Path(o) => o.draw(backend),
+            Object::Group(o) => o.draw(backend),
+            Object::ValueTracker(o) => o.draw(backend),
+        }
+    }
+}
+
+impl GetPath for Object {
+    fn path(&self) -> Path {
+        match self {
+            Object::Circle(o) => o.path(),
+            Object::Rectangle(o) => o.path(),
+            Object::Text(o) => o.path(),
+            Object::MathTex(o) => o.path(),
+            Object::Line(o) => o.path(),
+            Object::Polygon(o) => o.path(),
+            Object::Arc(o) => o.path(),
+            Object::Ellipse(o) => o.path(),
+            Object::Path(o) => o.path(),
+            Object::Group(o) => o.path(),
+            Object::ValueTracker(o) => o.path(),
+        }
+    }
+}
+
+impl GetColor for Object {
+    fn color(&self) -> Rgb {
+        match self {
+            Object::Circle(o) => GetColor::color(o),
+            Object::Rectangle(o) => GetColor::color(o),
+            Object::Text(o) => GetColor::color(o),
+            Object::MathTex(o) => GetColor::color(o),
+            Object::Line(o) => GetColor::color(o),
+            Object::Polygon(o) => GetColor::color(o),
+            Object::Arc(o) => GetColor::color(o),
+            Object::Ellipse(o) => GetColor::color(o),
+            Object::Path(o) => GetColor::color(o),
+            Object::Group(o) => GetColor::color(o),
+            Object::ValueTracker(o) => GetColor::color(o),
+        }
+    }
+    fn stroke_color(&self) -> Rgb {
+        match self {
+            Object::Circle(o) => GetColor::stroke_color(o),
+            Object::Rectangle(o) => GetColor::stroke_color(o),
+            Object::Text(o) => GetColor::stroke_color(o),
+            Object::MathTex(o) => GetColor::stroke_color(o),
+            Object::Line(o) => GetColor::stroke_color(o),
+            Object::Polygon(o) => GetColor::stroke_color(o),
+            Object::Arc(o) => GetColor::stroke_color(o),
+            Object::Ellipse(o) => GetColor::stroke_color(o),
+            Object::Path(o) => GetColor::stroke_color(o),
+            Object::Group(o) => GetColor::stroke_color(o),
+            Object::ValueTracker(o) => GetColor::stroke_color(o),
+        }
+    }
+    fn stroke_weight(&self) -> f32 {
+        match self {
+            Object::Circle(o) => GetColor::stroke_weight(o),
+            Object::Rectangle(o) => GetColor::stroke_weight(o),
+            Object::Text(o) => GetColor::stroke_weight(o),
+            Object::MathTex(o) => GetColor::stroke_weight(o),
+            Object::Line(o) => GetColor::stroke_weight(o),
+            Object::Polygon(o) => GetColor::stroke_weight(o),
+            Object::Arc(o) => GetColor::stroke_weight(o),
+            Object::Ellipse(o) => GetColor::stroke_weight(o),
+            Object::Path(o) => GetColor::stroke_weight(o),
+            Object::Group(o) => GetColor::stroke_weight(o),
+            Object::ValueTracker(o) => GetColor::stroke_weight(o),
+        }
+    }
+    fn fill_paint(&self) -> Paint {
+        match self {
+            Object::Circle(o) => GetColor::fill_paint(o),
+            Object::Rectangle(o) => GetColor::fill_paint(o),
+            Object::Text(o) => GetColor::fill_paint(o),
+            Object::MathTex(o) => GetColor::fill_paint(o),
+            Object::Line(o) => GetColor::fill_paint(o),
+            Object::Polygon(o) => GetColor::fill_paint(o),
+            Object::Arc(o) => GetColor::fill_paint(o),
+            Object::Ellipse(o) => GetColor::fill_paint(o),
+            Object::Path(o) => GetColor::fill_paint(o),
+            Object::Group(o) => GetColor::fill_paint(o),
+            Object::ValueTracker(o) => GetColor::fill_paint(o),
+        }
+    }
+    fn stroke_paint(&self) -> Paint {
+        match self {
+            Object::Circle(o) => GetColor::stroke_paint(o),
+            Object::Rectangle(o) => GetColor::stroke_paint(o),
+            Object::Text(o) => GetColor::stroke_paint(o),
+            Object::MathTex(o) => GetColor::stroke_paint(o),
+            Object::Line(o) => GetColor::stroke_paint(o),
+            Object::Polygon(o) => GetColor::stroke_paint(o),
+            Object::Arc(o) => GetColor::stroke_paint(o),
+            Object::Ellipse(o) => GetColor::stroke_paint(o),
+            Object::Path(o) => GetColor::stroke_paint(o),
+            Object::Group(o) => GetColor::stroke_paint(o),
+            Object::ValueTracker(o) => GetColor::stroke_paint(o),
+        }
+    }
+}
+
+impl SetColor for Object {
+    fn set_fill_paint(&mut self, paint: Paint) {
+        match self {
+            Object::Circle(o) => SetColor::set_fill_paint(o, paint),
+            Object::Rectangle(o) => SetColor::set_fill_paint(o, paint),
+            Object::Text(o) => SetColor::set_fill_paint(o, paint),
+            Object::MathTex(o) => SetColor::set_fill_paint(o, paint),
+            Object::Line(o) => SetColor::set_fill_paint(o, paint),
+            Object::Polygon(o) => SetColor::set_fill_paint(o, paint),
+            Object::Arc(o) => SetColor::set_fill_paint(o, paint),
+            Object::Ellipse(o) => SetColor::set_fill_paint(o, paint),
+            Object::Path(o) => SetColor::set_fill_paint(o, paint),
+            Object::Group(o) => SetColor::set_fill_paint(o, paint),
+            Object::ValueTracker(o) => SetColor::set_fill_paint(o, paint),
+        }
+    }
+    fn set_stroke_paint(&mut self, paint: Paint) {
         match self {
-            Object::Circle(o) => o.draw(draw),
-            Object::Rectangle(o) => o.draw(draw),
-            Object::Text(o) => o.draw(draw),
+            Object::Circle(o) => SetColor::set_stroke_paint(o, paint),
+            Object::Rectangle(o) => SetColor::set_stroke_paint(o, paint),
+            Object::Text(o) => SetColor::set_stroke_paint(o, paint),
+            Object::MathTex(o) => SetColor::set_stroke_paint(o, paint),
+            Object::Line(o) => SetColor::set_stroke_paint(o, paint),
+            Object::Polygon(o) => SetColor::set_stroke_paint(o, paint),
+            Object::Arc(o) => SetColor::set_stroke_paint(o, paint),
+            Object::Ellipse(o) => SetColor::set_stroke_paint(o, paint),
+            Object::Path(o) => SetColor::set_stroke_paint(o, paint),
+            Object::Group(o) => SetColor::set_stroke_paint(o, paint),
+            Object::ValueTracker(o) => SetColor::set_stroke_paint(o, paint),
+        }
+    }
+    fn set_stroke_weight(&mut self, weight: f32) {
+        match self {
+            Object::Circle(o) => SetColor::set_stroke_weight(o, weight),
+            Object::Rectangle(o) => SetColor::set_stroke_weight(o, weight),
+            Object::Text(o) => SetColor::set_stroke_weight(o, weight),
+            Object::MathTex(o) => SetColor::set_stroke_weight(o, weight),
+            Object::Line(o) => SetColor::set_stroke_weight(o, weight),
+            Object::Polygon(o) => SetColor::set_stroke_weight(o, weight),
+            Object::Arc(o) => SetColor::set_stroke_weight(o, weight),
+            Object::Ellipse(o) => SetColor::set_stroke_weight(o, weight),
+            Object::Path(o) => SetColor::set_stroke_weight(o, weight),
+            Object::Group(o) => SetColor::set_stroke_weight(o, weight),
+            Object::ValueTracker(o) => SetColor::set_stroke_weight(o, weight),
         }
     }
 }
@@ -135,6 +412,14 @@ impl GetOpacity for Object {
             Object::Circle(o) => GetOpacity::opacity(o),
             Object::Rectangle(o) => GetOpacity::opacity(o),
             Object::Text(o) => GetOpacity::opacity(o),
+            Object::MathTex(o) => GetOpacity::opacity(o),
+            Object::Line(o) => GetOpacity::opacity(o),
+            Object::Polygon(o) => GetOpacity::opacity(o),
+            Object::Arc(o) => GetOpacity::opacity(o),
+            Object::Ellipse(o) => GetOpacity::opacity(o),
+            Object::Path(o) => GetOpacity::opacity(o),
+            Object::Group(o) => GetOpacity::opacity(o),
+            Object::ValueTracker(o) => GetOpacity::opacity(o),
         }
     }
     fn is_visible(&self) -> bool {
@@ -142,6 +427,14 @@ impl GetOpacity for Object {
             Object::Circle(o) => GetOpacity::is_visible(o),
             Object::Rectangle(o) => GetOpacity::is_visible(o),
             Object::Text(o) => GetOpacity::is_visible(o),
+            Object::MathTex(o) => GetOpacity::is_visible(o),
+            Object::Line(o) => GetOpacity::is_visible(o),
+            Object::Polygon(o) => GetOpacity::is_visible(o),
+            Object::Arc(o) => GetOpacity::is_visible(o),
+            Object::Ellipse(o) => GetOpacity::is_visible(o),
+            Object::Path(o) => GetOpacity::is_visible(o),
+            Object::Group(o) => GetOpacity::is_visible(o),
+            Object::ValueTracker(o) => GetOpacity::is_visible(o),
         }
     }
 }
@@ -152,6 +445,14 @@ impl SetOpacity for Object {
             Object::Circle(o) => SetOpacity::opacity_mut(o),
             Object::Rectangle(o) => SetOpacity::opacity_mut(o),
             Object::Text(o) => SetOpacity::opacity_mut(o),
+            Object::MathTex(o) => SetOpacity::opacity_mut(o),
+            Object::Line(o) => SetOpacity::opacity_mut(o),
+            Object::Polygon(o) => SetOpacity::opacity_mut(o),
+            Object::Arc(o) => SetOpacity::opacity_mut(o),
+            Object::Ellipse(o) => SetOpacity::opacity_mut(o),
+            Object::Path(o) => SetOpacity::opacity_mut(o),
+            Object::Group(o) => SetOpacity::opacity_mut(o),
+            Object::ValueTracker(o) => SetOpacity::opacity_mut(o),
         }
     }
 }
@@ -174,6 +475,54 @@ impl From<Text> for Object {
     }
 }
 
+impl From<MathTex> for Object {
+    fn from(m: MathTex) -> Self {
+        Object::MathTex(m)
+    }
+}
+
+impl From<Line> for Object {
+    fn from(o: Line) -> Self {
+        Object::Line(o)
+    }
+}
+
+impl From<Polygon> for Object {
+    fn from(o: Polygon) -> Self {
+        Object::Polygon(o)
+    }
+}
+
+impl From<Arc> for Object {
+    fn from(o: Arc) -> Self {
+        Object::Arc(o)
+    }
+}
+
+impl From<Ellipse> for Object {
+    fn from(o: Ellipse) -> Self {
+        Object::Ellipse(o)
+    }
+}
+
+impl From<VPath> for Object {
+    fn from(p: VPath) -> Self {
+        Object::Path(p)
+    }
+}
+
+impl From<Group> for Object {
+    fn from(g: Group) -> Self {
+        Object::Group(g)
+    }
+}
+
+impl From<ValueTracker> for Object {
+    fn from(v: ValueTracker) -> Self {
+        Object::ValueTracker(v)
+    }
+}
+
 // impl PathCompletion for NodeIndex {
 //     fn completion(&self) -> f32 {
 //         PathCompletion::completion(&*self.clone().borrow_mut())
@@ -0,0 +1,246 @@
+use crate::animation::PathCompletion;
+use crate::appearance::{GetColor, GetOpacity, Opacity, Paint, SetColor, SetOpacity};
+use crate::arena::Object;
+use crate::consts::*;
+use crate::draw::{draw_outline, Backend, Draw, PathStyle, Transform};
+use crate::geom;
+use crate::geom::{dimension, Dimension, GetDimension, SetDimension};
+use crate::geom::{GetOrientation, GetPosition, SetOrientation, SetPosition};
+use crate::path::{GetPath, MeasureCache};
+
+use nannou;
+use nannou::color::Rgb;
+use nannou::lyon::math as lyon;
+use nannou::lyon::math::point;
+use nannou::lyon::path::Path;
+
+/// Closed shape through a list of vertices.
+/// Vertices are kept relative to the size of the polygon, so resizing
+/// stretches the shape like any other object.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Polygon {
+    vertices: Vec<lyon::Point>, // Within box of unit size around origin
+    dimension: Dimension,
+    position: geom::Point,
+    orientation: f32,
+    path_completion: f32,
+    color: Paint,
+    stroke_color: Paint,
+    stroke_weight: f32,
+    opacity: Opacity,
+    measured: MeasureCache,
+}
+
+impl Polygon {
+    fn new(vertices: &[geom::Point]) -> Self {
+        let (position, dimension, vertices) = normalize(vertices);
+        Polygon {
+            vertices,
+            dimension,
+            position,
+            orientation: 0.0,
+            path_completion: 1.0,
+            color: Paint::Solid(DEFAULT_FILL_COLOR),
+            stroke_color: Paint::Solid(DEFAULT_STROKE_COLOR),
+            stroke_weight: DEFAULT_STROKE_WEIGHT,
+            opacity: Opacity::new(false),
+            measured: MeasureCache::default(),
+        }
+    }
+    /// Polygon with `sides` equal sides, with a vertex pointing up
+    fn regular(sides: usize, radius: f32) -> Self {
+        let sides = sides.max(3);
+        let vertices: Vec<geom::Point> = (0..sides)
+            .map(|i| {
+                let angle = (90.0 + 360.0 * i as f32 / sides as f32).to_radians();
+                geom::point_at(radius * angle.cos(), radius * angle.sin())
+            })
+            .collect();
+        let mut polygon = Self::new(&vertices);
+        polygon.position = geom::point();
+        polygon
+    }
+    /// Vertices in local coordinates of the object
+    pub fn vertices(&self) -> Vec<geom::Point> {
+        self.vertices
+            .iter()
+            .map(|v| geom::point_at(v.x * self.width(), v.y * self.height()))
+            .collect()
+    }
+}
+
+/// Fit `points` into a box of unit size around origin.
+/// Returns center and size of the original points, along with fitted points.
+pub fn normalize(points: &[geom::Point]) -> (geom::Point, Dimension, Vec<lyon::Point>) {
+    if points.is_empty() {
+        return (geom::point(), dimension(0.0, 0.0), Vec::new());
+    }
+    let (mut min, mut max) = (points[0], points[0]);
+    for p in points.iter() {
+        min = geom::point_at(min.x.min(p.x), min.y.min(p.y));
+        max = geom::point_at(max.x.max(p.x), max.y.max(p.y));
+    }
+    let center = (min + max) * 0.5;
+    let (width, height) = (max.x - min.x, max.y - min.y);
+    // Flat extent stays flat at any size
+    let fit = |value: f32, extent: f32| if extent > 0.0 { value / extent } else { 0.0 };
+    let fitted = points
+        .iter()
+        .map(|p| point(fit(p.x - center.x, width), fit(p.y - center.y, height)))
+        .collect();
+    (center, dimension(width, height), fitted)
+}
+
+impl GetPath for Polygon {
+    fn path(&self) -> Path {
+        let mut builder = Path::builder();
+        let vertices = self.vertices();
+        if let Some(first) = vertices.first() {
+            builder.move_to((*first).into());
+            for v in vertices.iter().skip(1) {
+                builder.line_to((*v).into());
+            }
+            builder.close();
+        }
+        builder.build()
+    }
+}
+
+impl Draw for Polygon {
+    fn draw<B: Backend>(&self, backend: &mut B) {
+        if self.is_visible() {
+            let measured = self.measured.get(|| self.path());
+            let path = measured.upto(self.path_completion);
+            let transform = Transform::new(self.position, self.orientation);
+            draw_outline(
+                backend,
+                measured.path(),
+                &path,
+                &transform,
+                PathStyle::of(self),
+            );
+        }
+    }
+}
+
+impl PathCompletion for Polygon {
+    fn completion(&self) -> f32 {
+        self.path_completion
+    }
+    fn set_completion(&mut self, completion: f32) {
+        self.path_completion = completion.max(0.0).min(1.0);
+    }
+}
+
+impl SetPosition for Polygon {
+    fn position_mut(&mut self) -> &mut geom::Point {
+        SetPosition::position_mut(&mut self.position)
+    }
+}
+
+impl GetPosition for Polygon {
+    fn position(&self) -> geom::Point {
+        GetPosition::position(&self.position)
+    }
+}
+
+impl GetOrientation for Polygon {
+    fn orientation(&self) -> f32 {
+        self.orientation
+    }
+}
+
+impl SetOrientation for Polygon {
+    fn orientation_mut(&mut self) -> &mut f32 {
+        &mut self.orientation
+    }
+}
+
+impl GetDimension for Polygon {
+    fn dimension(&self) -> &Dimension {
+        GetDimension::dimension(&self.dimension)
+    }
+}
+
+impl SetDimension for Polygon {
+    fn dimension_mut(&mut self) -> &mut Dimension {
+        self.measured.clear();
+        SetDimension::dimension_mut(&mut self.dimension)
+    }
+}
+
+impl GetColor for Polygon {
+    fn color(&self) -> Rgb {
+        self.color.color()
+    }
+    fn stroke_color(&self) -> Rgb {
+        self.stroke_color.color()
+    }
+    fn stroke_weight(&self) -> f32 {
+        self.stroke_weight
+    }
+    fn fill_paint(&self) -> Paint {
+        self.color.clone()
+    }
+    fn stroke_paint(&self) -> Paint {
+        self.stroke_color.clone()
+    }
+}
+
+impl SetColor for Polygon {
+    fn set_fill_paint(&mut self, paint: Paint) {
+        self.color = paint;
+    }
+    fn set_stroke_paint(&mut self, paint: Paint) {
+        self.stroke_color = paint;
+    }
+    fn set_stroke_weight(&mut self, weight: f32) {
+        self.stroke_weight = weight.max(0.0);
+    }
+}
+
+impl GetOpacity for Polygon {
+    fn opacity(&self) -> f32 {
+        GetOpacity::opacity(&self.opacity)
+    }
+    fn is_visible(&self) -> bool {
+        GetOpacity::is_visible(&self.opacity)
+    }
+}
+
+impl SetOpacity for Polygon {
+    fn opacity_mut(&mut self) -> &mut Opacity {
+        SetOpacity::opacity_mut(&mut self.opacity)
+    }
+}
+
+pub fn polygon(vertices: &[geom::Point]) -> Object {
+    Object::new(Polygon::new(vertices).into())
+}
+
+pub fn regular_polygon(sides: usize) -> Object {
+    Object::new(Polygon::regular(sides, DEFAULT_POLYGON_RADIUS).into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geom::point_at;
+
+    #[test]
+    fn resize_keeps_shape() {
+        let vertices = [
+            point_at(10.0, 10.0),
+            point_at(50.0, 10.0),
+            point_at(10.0, 30.0),
+        ];
+        let mut triangle = Polygon::new(&vertices);
+        assert_eq!(triangle.position(), point_at(30.0, 20.0));
+        assert_eq!((triangle.width(), triangle.height()), (40.0, 20.0));
+
+        triangle.set_width(80.0);
+        let local = triangle.vertices();
+        assert_eq!(local[1], point_at(40.0, -10.0));
+        assert_eq!(local[2], point_at(-40.0, 10.0));
+    }
+}
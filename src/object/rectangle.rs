@@ -1,27 +1,29 @@
 use crate::animation::PathCompletion;
-use crate::appearance::{GetOpacity, Opacity, SetOpacity};
+use crate::appearance::{GetColor, GetOpacity, Opacity, Paint, SetColor, SetOpacity};
 use crate::arena::Object;
 use crate::consts::*;
-use crate::draw::Draw;
+use crate::draw::{draw_outline, Backend, Draw, PathStyle, Transform};
 use crate::geom;
 use crate::geom::{dimension, Dimension, GetDimension, SetDimension};
 use crate::geom::{GetOrientation, GetPosition, SetOrientation, SetPosition};
-use crate::path::GetPartial;
+use crate::path::{GetPath, MeasureCache};
 
 use nannou;
-use nannou::color::{Rgb, Rgba};
+use nannou::color::Rgb;
 use nannou::lyon::math::point;
 use nannou::lyon::path::Path;
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Rectangle {
     position: geom::Point,
     orientation: f32,
     dimension: Dimension,
     path_completion: f32,
-    color: Rgb,
-    stroke_color: Rgb,
+    color: Paint,
+    stroke_color: Paint,
+    stroke_weight: f32,
     opacity: Opacity,
+    measured: MeasureCache,
 }
 
 impl Rectangle {
@@ -31,55 +33,44 @@ impl Rectangle {
             orientation: 0.0,
             position: geom::point(),
             path_completion: 1.0,
-            color: RED_D,
-            stroke_color: DEFAULT_STROKE_COLOR,
+            color: Paint::Solid(RED_D),
+            stroke_color: Paint::Solid(DEFAULT_STROKE_COLOR),
+            stroke_weight: DEFAULT_STROKE_WEIGHT,
             opacity: Opacity::new(false),
+            measured: MeasureCache::default(),
         }
     }
 }
 
+impl GetPath for Rectangle {
+    fn path(&self) -> Path {
+        let mut builder = Path::builder();
+        let start = point(-self.width() / 2.0, self.height() / 2.0);
+
+        builder.move_to(start);
+        builder.line_to(point(start.x + self.width(), start.y));
+        builder.line_to(point(start.x + self.width(), start.y - self.height()));
+        builder.line_to(point(start.x, start.y - self.height()));
+        builder.line_to(point(start.x, start.y));
+        builder.close();
+
+        builder.build()
+    }
+}
+
 impl Draw for Rectangle {
-    fn draw(&self, draw: nannou::Draw) {
+    fn draw<B: Backend>(&self, backend: &mut B) {
         if self.is_visible() {
-            let mut builder = Path::builder();
-            let start = point(-self.width() / 2.0, self.height() / 2.0);
-
-            builder.move_to(start);
-            builder.line_to(point(start.x + self.width(), start.y));
-            builder.line_to(point(start.x + self.width(), start.y - self.height()));
-            builder.line_to(point(start.x, start.y - self.height()));
-            builder.line_to(point(start.x, start.y));
-            builder.close();
-
-            let path = builder.build();
-            let path = path.upto(self.path_completion, DEFAULT_FLATTEN_TOLERANCE);
-
-            let color = Rgba {
-                color: self.color,
-                alpha: self.alpha(),
-            };
-
-            let stroke_color = Rgba {
-                color: self.stroke_color,
-                alpha: self.alpha(),
-            };
-
-            // Draw fill first
-            draw.path()
-                .fill()
-                .x_y(self.position.x, self.position.y)
-                .z_degrees(self.orientation)
-                .color(color)
-                .events(&path);
-
-            // Draw stroke on top
-            draw.path()
-                .stroke()
-                .x_y(self.position.x, self.position.y)
-                .z_degrees(self.orientation)
-                .color(stroke_color)
-                .stroke_weight(DEFAULT_STROKE_WEIGHT)
-                .events(&path);
+            let measured = self.measured.get(|| self.path());
+            let path = measured.upto(self.path_completion);
+            let transform = Transform::new(self.position, self.orientation);
+            draw_outline(
+                backend,
+                measured.path(),
+                &path,
+                &transform,
+                PathStyle::of(self),
+            );
         }
     }
 }
@@ -125,10 +116,41 @@ impl GetDimension for Rectangle {
 
 impl SetDimension for Rectangle {
     fn dimension_mut(&mut self) -> &mut Dimension {
+        self.measured.clear();
         SetDimension::dimension_mut(&mut self.dimension)
     }
 }
 
+impl GetColor for Rectangle {
+    fn color(&self) -> Rgb {
+        self.color.color()
+    }
+    fn stroke_color(&self) -> Rgb {
+        self.stroke_color.color()
+    }
+    fn stroke_weight(&self) -> f32 {
+        self.stroke_weight
+    }
+    fn fill_paint(&self) -> Paint {
+        self.color.clone()
+    }
+    fn stroke_paint(&self) -> Paint {
+        self.stroke_color.clone()
+    }
+}
+
+impl SetColor for Rectangle {
+    fn set_fill_paint(&mut self, paint: Paint) {
+        self.color = paint;
+    }
+    fn set_stroke_paint(&mut self, paint: Paint) {
+        self.stroke_color = paint;
+    }
+    fn set_stroke_weight(&mut self, weight: f32) {
+        self.stroke_weight = weight.max(0.0);
+    }
+}
+
 impl GetOpacity for Rectangle {
     fn opacity(&self) -> f32 {
         GetOpacity::opacity(&self.opacity)
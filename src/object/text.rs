@@ -1,30 +1,144 @@
-use crate::animation::PathCompletion;
-use crate::appearance::{GetOpacity, Opacity, SetOpacity};
+use crate::animation::{Interpolate, PathCompletion};
+use crate::appearance::{GetColor, GetOpacity, Opacity, Paint, SetColor, SetOpacity};
 use crate::arena::Object;
 use crate::consts::*;
-use crate::draw::Draw;
+use crate::draw::{Backend, Draw, PathStyle, Transform};
 use crate::geom;
 use crate::geom::{dimension, Dimension, GetDimension, SetDimension};
 use crate::geom::{point_at, GetOrientation, GetPosition, SetOrientation, SetPosition};
-use crate::path::GetPartial;
+use crate::import::{FontFamily, Slant, Weight};
+use crate::path::{concat, map_points, outlines, partials, same_path, Bounds, GetPath, Measured};
 
 use nannou;
-use nannou::color::{Rgb, Rgba};
+use nannou::color::Rgb;
+use nannou::lyon::math::point;
 // use nannou::lyon::math::{point, Angle, Vector};
 use nannou::lyon::path::builder::PathBuilder;
 use nannou::lyon::path::Path;
 // use nannou::prelude::*;
 
-#[derive(Debug, PartialEq)]
+use std::cell::RefCell;
+use std::collections::{BTreeMap, HashMap};
+use std::ops::Range;
+use std::sync::Arc;
+
+// Extent of the layout in directions without a limit
+const UNBOUNDED: f32 = 1.0e4;
+
+// Laid out text, shared between texts and frames. Outlines are measured
+// once, so any part of them is drawn without flattening them again.
+#[derive(Debug)]
+struct Outline {
+    path: Measured,
+    glyphs: Vec<Measured>,          // Outlines of `path` by glyph
+    boxes: Vec<nannou::geom::Rect>, // Box of every glyph
+    size: Dimension,
+}
+
+impl Outline {
+    fn new(path: &Path, boxes: Vec<nannou::geom::Rect>, size: Dimension) -> Self {
+        let measure = |path: &Path| Measured::new(path, DEFAULT_FLATTEN_TOLERANCE);
+        Self {
+            path: measure(path),
+            glyphs: group_glyphs(path, &boxes).iter().map(measure).collect(),
+            boxes,
+            size,
+        }
+    }
+    // Part of every glyph drawn when the whole text is drawn up to
+    // `completion`, glyphs being drawn one after another
+    fn partial_glyphs(&self, completion: f32) -> Vec<Path> {
+        partials(&self.glyphs, completion)
+    }
+}
+
+// Layouts are equal if their outlines are, as paths can't be compared
+impl PartialEq for Outline {
+    fn eq(&self, other: &Self) -> bool {
+        self.size == other.size && same_path(self.path.path(), other.path.path())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct LayoutKey {
+    string: String,
+    face: String,
+    font_size: u32,
+    alignment: Alignment,
+    line_spacing: u32, // Bits of floats, which can't be hashed
+    max_width: Option<u32>,
+}
+
+thread_local! {
+    static LAYOUTS: RefCell<HashMap<LayoutKey, Arc<Outline>>> = RefCell::new(HashMap::new());
+}
+
+/// Horizontal alignment of the lines of a `Text`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Alignment {
+    Left,
+    Center,
+    Right,
+}
+
+/// Font and layout of a `Text`
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextStyle {
+    pub font: FontFamily,
+    pub weight: Weight,
+    pub slant: Slant,
+    pub alignment: Alignment,
+    pub line_spacing: f32, // Distance between baselines, relative to font size
+    pub max_width: Option<f32>, // Lines longer than this wrap between words
+}
+
+impl Default for TextStyle {
+    fn default() -> Self {
+        Self {
+            font: FontFamily::default(),
+            weight: Weight::Normal,
+            slant: Slant::Normal,
+            alignment: Alignment::Left,
+            line_spacing: 1.0,
+            max_width: None,
+        }
+    }
+}
+
+impl Interpolate for TextStyle {
+    fn interp_mut(&mut self, other: &Self, progress: f32) {
+        *self = self.interp(other, progress);
+    }
+    // Spacing and width blend, everything else switches halfway
+    fn interp(&self, other: &Self, progress: f32) -> Self {
+        let mut style = if progress < 0.5 {
+            self.clone()
+        } else {
+            other.clone()
+        };
+        style.line_spacing = self.line_spacing.interp(&other.line_spacing, progress);
+        if let (Some(from), Some(to)) = (self.max_width, other.max_width) {
+            style.max_width = Some(from.interp(&to, progress));
+        }
+        style
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
 pub struct Text {
     string: String,
     font_size: u32,
+    style: TextStyle,
+    outline: Arc<Outline>,
     dimension: Dimension,
     position: geom::Point,
     orientation: f32,
     path_completion: f32,
-    color: Rgb,
-    stroke_color: Rgb,
+    write_progress: Option<f32>, // Progress of `Write` animation, if running
+    color: Paint,
+    glyph_colors: BTreeMap<usize, Paint>, // Fills of glyphs colored apart from the rest
+    stroke_color: Paint,
+    stroke_weight: f32,
     opacity: Opacity,
 }
 
@@ -33,12 +147,17 @@ impl Text {
         let mut text = Text {
             string: String::from(text),
             font_size: 90,
-            dimension: dimension(2000.0, 2000.0),
+            style: TextStyle::default(),
+            outline: Arc::new(Outline::new(&Path::new(), Vec::new(), dimension(0.0, 0.0))),
+            dimension: dimension(0.0, 0.0),
             position: point_at(0.0, 0.0),
             orientation: 0.0,
             path_completion: 1.0,
-            color: DEFAULT_FILL_COLOR,
-            stroke_color: DEFAULT_STROKE_COLOR,
+            write_progress: None,
+            color: Paint::Solid(DEFAULT_FILL_COLOR),
+            glyph_colors: BTreeMap::new(),
+            stroke_color: Paint::Solid(DEFAULT_STROKE_COLOR),
+            stroke_weight: DEFAULT_TEXT_STROKE_WEIGHT,
             opacity: Opacity::new(false),
         };
         text.update_size();
@@ -49,6 +168,8 @@ impl Text {
     }
     pub fn set_text(&mut self, text: &str) {
         self.string = String::from(text);
+        let count = self.glyph_count();
+        self.glyph_colors.split_off(&count);
         self.update_size();
     }
     pub fn set_font_size(&mut self, size: u32) {
@@ -58,43 +179,194 @@ impl Text {
     pub fn font_size(&self) -> u32 {
         self.font_size
     }
-    fn update_size(&mut self) {
-        let rect = nannou::geom::Rect::from_w_h(self.width(), self.height());
-        let text = nannou::text::text(&self.string)
+    pub fn style(&self) -> &TextStyle {
+        &self.style
+    }
+    pub fn set_style(&mut self, style: TextStyle) {
+        self.style = style;
+        self.update_size();
+    }
+    pub fn write_progress(&self) -> Option<f32> {
+        self.write_progress
+    }
+    /// Set progress of `Write` animation, or `None` to draw normally
+    pub fn set_write_progress(&mut self, progress: Option<f32>) {
+        self.write_progress = progress.map(|p| p.max(0.0).min(1.0));
+    }
+    /// Number of glyphs, i.e. characters other than whitespace
+    pub fn glyph_count(&self) -> usize {
+        self.string.chars().filter(|c| !c.is_whitespace()).count()
+    }
+    /// Glyphs of every word, i.e. run of characters other than whitespace
+    pub fn word_glyphs(&self) -> Vec<Range<usize>> {
+        let mut start = 0;
+        self.string
+            .split_whitespace()
+            .map(|word| {
+                let glyphs = start..start + word.chars().count();
+                start = glyphs.end;
+                glyphs
+            })
+            .collect()
+    }
+    /// Outline of every glyph, relative to the position of the text
+    pub fn glyph_paths(&self) -> Vec<Path> {
+        self.outline
+            .glyphs
+            .iter()
+            .map(|glyph| glyph.path().clone())
+            .collect()
+    }
+    /// Indices of glyphs in every occurrence of `pattern`, like manim's `t2c`
+    pub fn glyph_indices(&self, pattern: &str) -> Vec<usize> {
+        let mut indices = Vec::new();
+        if pattern.is_empty() {
+            return indices;
+        }
+        let visible = |s: &str| s.chars().filter(|c| !c.is_whitespace()).count();
+        for (start, matched) in self.string.match_indices(pattern) {
+            let first = visible(&self.string[..start]);
+            indices.extend(first..first + visible(matched));
+        }
+        indices
+    }
+    /// Fill of glyph `index`, which is the fill of the text unless set apart
+    pub fn glyph_color(&self, index: usize) -> Paint {
+        self.glyph_colors
+            .get(&index)
+            .cloned()
+            .unwrap_or_else(|| self.color.clone())
+    }
+    pub fn set_glyph_color(&mut self, index: usize, paint: Paint) {
+        if index < self.glyph_count() {
+            self.glyph_colors.insert(index, paint);
+        }
+    }
+    /// Fill all glyphs with the fill of the text again
+    pub fn clear_glyph_colors(&mut self) {
+        self.glyph_colors.clear();
+    }
+    // Layout of the text, shared by texts of the same string and style
+    fn layout_key(&self) -> LayoutKey {
+        let style = &self.style;
+        LayoutKey {
+            string: self.string.clone(),
+            face: String::from(style.font.source(style.weight, style.slant)),
+            font_size: self.font_size,
+            alignment: style.alignment,
+            line_spacing: style.line_spacing.to_bits(),
+            max_width: style.max_width.map(f32::to_bits),
+        }
+    }
+}
+
+// Outlines of `path` grouped by the glyph they belong to, in order of the
+// string. Each outline goes to the glyph box containing its center.
+fn group_glyphs(path: &Path, boxes: &[nannou::geom::Rect]) -> Vec<Path> {
+    if boxes.is_empty() {
+        return outlines(path);
+    }
+    let mut glyphs = vec![Vec::new(); boxes.len()];
+    for outline in outlines(path) {
+        let center = Bounds::of_path(&outline).center();
+        let distance = |rect: &nannou::geom::Rect| (rect.x() - center.x).hypot(rect.y() - center.y);
+        let nearest = boxes
+            .iter()
+            .enumerate()
+            .min_by(|(_, a), (_, b)| distance(a).partial_cmp(&distance(b)).unwrap())
+            .map(|(i, _)| i)
+            .unwrap_or(0);
+        let index = boxes
+            .iter()
+            .position(|rect| rect.contains(nannou::geom::pt2(center.x, center.y)))
+            .unwrap_or(nearest);
+        glyphs[index].push(outline);
+    }
+    glyphs.iter().map(|outlines| concat(outlines)).collect()
+}
+
+impl Text {
+    // Lines of the text laid out by nannou
+    fn layout(&self) -> nannou::text::Text {
+        let style = &self.style;
+        let width = style.max_width.unwrap_or(UNBOUNDED);
+        let rect = nannou::geom::Rect::from_w_h(width, UNBOUNDED);
+        let builder = nannou::text::text(&self.string)
+            .font(style.font.select(style.weight, style.slant))
             .font_size(self.font_size)
-            .left_justify()
-            .build(rect);
+            .line_spacing((style.line_spacing - 1.0) * self.font_size as f32);
+        let builder = match style.alignment {
+            Alignment::Left => builder.left_justify(),
+            Alignment::Center => builder.center_justify(),
+            Alignment::Right => builder.right_justify(),
+        };
+        let builder = match style.max_width {
+            Some(_) => builder.wrap_by_word(),
+            None => builder.no_line_wrap(),
+        };
+        builder.build(rect)
+    }
+    // Outline of the text with the bounds of the layout centered on the
+    // origin, and the glyphs it is made of
+    fn build_outline(&self) -> Outline {
+        let text = self.layout();
         let bbox = text.bounding_rect();
-        self.dimension = dimension(bbox.w(), bbox.h());
+        let center = bbox.xy();
+
+        let mut builder = Path::builder();
+        for e in text.path_events() {
+            builder.path_event(e);
+        }
+        builder.close();
+
+        let path = map_points(&builder.build(), |p| point(p.x - center.x, p.y - center.y));
+        let boxes: Vec<_> = text
+            .glyphs()
+            .map(|(_, rect)| rect.shift(-center))
+            .filter(|rect| rect.w() > 0.0 && rect.h() > 0.0)
+            .collect();
+        Outline::new(&path, boxes, dimension(bbox.w(), bbox.h()))
+    }
+    // Lay out the text again, unless a text of the same string and style
+    // was laid out before
+    fn update_size(&mut self) {
+        let key = self.layout_key();
+        let cached = LAYOUTS.with(|layouts| layouts.borrow().get(&key).cloned());
+        self.outline = match cached {
+            Some(outline) => outline,
+            None => {
+                let outline = Arc::new(self.build_outline());
+                LAYOUTS.with(|layouts| {
+                    let mut layouts = layouts.borrow_mut();
+                    if layouts.len() >= TEXT_LAYOUT_CACHE_SIZE {
+                        layouts.clear();
+                    }
+                    layouts.insert(key, outline.clone());
+                });
+                outline
+            }
+        };
+        self.dimension = self.outline.size;
+    }
+}
+
+impl GetPath for Text {
+    fn path(&self) -> Path {
+        self.outline.path.path().clone()
     }
 }
 
 impl Draw for Text {
-    fn draw(&self, draw: nannou::Draw) {
+    fn draw<B: Backend>(&self, backend: &mut B) {
         if self.is_visible() {
-            let rect = nannou::geom::Rect::from_w_h(self.width(), self.height());
-            let text = nannou::text::text(&self.string)
-                .font_size(self.font_size)
-                .left_justify()
-                .build(rect);
-
-            let mut builder = Path::builder();
-            for e in text.path_events() {
-                builder.path_event(e);
-            }
-            builder.close();
-
-            let path = builder.build();
-            let path = path.upto(self.path_completion, DEFAULT_FLATTEN_TOLERANCE);
+            let transform = Transform::new(self.position, self.orientation);
+            let style = PathStyle::of(self).fit(self.outline.path.path());
 
-            let color = Rgba {
-                color: self.color,
-                alpha: self.alpha(),
-            };
-            let stroke_color = Rgba {
-                color: self.stroke_color,
-                alpha: self.alpha(),
-            };
+            if let Some(progress) = self.write_progress {
+                self.draw_writing(backend, &transform, &style, progress);
+                return;
+            }
+            let path = self.outline.path.upto(self.path_completion);
 
             // let bbox = text.bounding_rect();
             // draw.rect()
@@ -103,30 +375,68 @@ impl Draw for Text {
             //     .w_h(self.width(), self.height())
             //     .color(RED_D);
 
-            // for (_glyph, rect) in text.glyphs() {
-            //     draw.rect()
-            //         .x_y(rect.x() + self.position.x, rect.y() + self.position.y)
-            //         .wh(rect.wh())
-            //         .hsla(0.5, 1.0, 0.5, 0.5);
-            // }
-
-            draw.path()
-                .fill()
-                .x_y(self.position.x, self.position.y)
-                .z_degrees(self.orientation)
-                .color(color)
-                .events(&path);
-            draw.path()
-                .stroke()
-                .x_y(self.position.x, self.position.y)
-                .z_degrees(self.orientation)
-                .color(stroke_color)
-                .stroke_weight(DEFAULT_TEXT_STROKE_WEIGHT)
-                .events(&path);
+            if self.glyph_colors.is_empty() {
+                style.fill(backend, &path, &transform);
+            } else {
+                let partial = self.outline.partial_glyphs(self.path_completion);
+                for (i, (glyph, part)) in self.outline.glyphs.iter().zip(partial.iter()).enumerate()
+                {
+                    let paint = self.glyph_paint(i, glyph.path(), &style.fill);
+                    backend.fill_paint(part, &transform, &paint, style.alpha);
+                }
+            }
+            style.stroke(backend, &path, &transform);
+        }
+    }
+}
+
+impl Text {
+    // Glyphs are written one after another, overlapping in time. Each glyph
+    // first traces its stroke, then fades in its fill.
+    fn draw_writing<B: Backend>(
+        &self,
+        backend: &mut B,
+        transform: &Transform,
+        style: &PathStyle,
+        progress: f32,
+    ) {
+        let count = self.outline.glyphs.len();
+        for (i, glyph) in self.outline.glyphs.iter().enumerate() {
+            let p = lagged_progress(progress, i, count);
+            if p <= 0.0 {
+                break;
+            }
+            let stroke = glyph.upto((p * 2.0).min(1.0));
+            let fill_alpha = (p * 2.0 - 1.0).max(0.0) * style.alpha;
+
+            if fill_alpha > 0.0 {
+                let paint = self.glyph_paint(i, glyph.path(), &style.fill);
+                backend.fill_paint(glyph.path(), transform, &paint, fill_alpha);
+            }
+            style.stroke(backend, &stroke, transform);
+        }
+    }
+    // Fill of a glyph set apart is fitted to the glyph alone, others share
+    // the fill of the whole text
+    fn glyph_paint(&self, index: usize, glyph: &Path, color: &Paint) -> Paint {
+        match self.glyph_colors.get(&index) {
+            Some(paint) => paint.fit(glyph),
+            None => color.clone(),
         }
     }
 }
 
+/// Progress of item `index` out of `count`, when items start one after
+/// another and each takes `WRITE_SPAN` of the whole duration.
+pub fn lagged_progress(progress: f32, index: usize, count: usize) -> f32 {
+    if count <= 1 {
+        return progress;
+    }
+    let span = WRITE_SPAN.max(1.0 / count as f32);
+    let lag = (1.0 - span) / (count - 1) as f32;
+    ((progress - index as f32 * lag) / span).max(0.0).min(1.0)
+}
+
 impl PathCompletion for Text {
     fn completion(&self) -> f32 {
         self.path_completion
@@ -169,6 +479,8 @@ impl SetDimension for Text {
         let scale_h = size.height() / self.height();
         let scale = scale_w.min(scale_h);
         let font_size = ((self.font_size as f32 * scale) as u32).max(0_u32);
+        // Lines wrap at the same words at any size
+        self.style.max_width = self.style.max_width.map(|width| width * scale);
         self.set_font_size(font_size);
     }
 }
@@ -185,6 +497,36 @@ impl SetOrientation for Text {
     }
 }
 
+impl GetColor for Text {
+    fn color(&self) -> Rgb {
+        self.color.color()
+    }
+    fn stroke_color(&self) -> Rgb {
+        self.stroke_color.color()
+    }
+    fn stroke_weight(&self) -> f32 {
+        self.stroke_weight
+    }
+    fn fill_paint(&self) -> Paint {
+        self.color.clone()
+    }
+    fn stroke_paint(&self) -> Paint {
+        self.stroke_color.clone()
+    }
+}
+
+impl SetColor for Text {
+    fn set_fill_paint(&mut self, paint: Paint) {
+        self.color = paint;
+    }
+    fn set_stroke_paint(&mut self, paint: Paint) {
+        self.stroke_color = paint;
+    }
+    fn set_stroke_weight(&mut self, weight: f32) {
+        self.stroke_weight = weight.max(0.0);
+    }
+}
+
 impl GetOpacity for Text {
     fn opacity(&self) -> f32 {
         GetOpacity::opacity(&self.opacity)
@@ -203,3 +545,57 @@ impl SetOpacity for Text {
 pub fn text(content: &str) -> Object {
     Object::new(Text::new(content).into())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::path::MeasureLength;
+
+    #[test]
+    fn wrapping_follows_max_width() {
+        let mut text = Text::new("wrap these words");
+        let line = (text.width(), text.height());
+
+        let mut style = text.style().clone();
+        style.max_width = Some(line.0 * 0.6);
+        text.set_style(style);
+        assert!(text.width() <= line.0 * 0.6);
+        assert!(text.height() > line.1 * 1.5);
+
+        // Path is centered on the position, like other objects
+        let bounds = Bounds::of_path(&text.path());
+        assert!(bounds.center().x.abs() < text.width() * 0.1);
+        assert!(bounds.center().y.abs() < text.height() * 0.1);
+    }
+
+    #[test]
+    fn layouts_are_shared() {
+        let a = Text::new("shared");
+        let mut b = Text::new("shared");
+        assert!(Arc::ptr_eq(&a.outline, &b.outline));
+
+        b.set_font_size(45);
+        assert!(!Arc::ptr_eq(&a.outline, &b.outline));
+        assert!(b.width() < a.width());
+        b.set_font_size(90);
+        assert!(Arc::ptr_eq(&a.outline, &b.outline));
+    }
+
+    #[test]
+    fn glyphs_are_drawn_in_order() {
+        let text = Text::new("ab");
+        let length = |paths: &[Path]| -> f32 {
+            paths
+                .iter()
+                .map(|path| path.approximate_length(DEFAULT_FLATTEN_TOLERANCE))
+                .sum()
+        };
+        let first = text.outline.glyphs[0].length();
+        let total = length(&text.glyph_paths());
+
+        let partial = text.outline.partial_glyphs(first / total);
+        assert!((length(&partial[..1]) - first).abs() < 0.01);
+        assert!(length(&partial[1..]) < 0.01);
+        assert!((length(&text.outline.partial_glyphs(1.0)) - total).abs() < 0.01);
+    }
+}
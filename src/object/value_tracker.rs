@@ -0,0 +1,129 @@
+use crate::animation::PathCompletion;
+use crate::appearance::{GetColor, GetOpacity, Opacity, Paint, SetColor, SetOpacity};
+use crate::arena::Object;
+use crate::consts::*;
+use crate::draw::{Backend, Draw};
+use crate::geom;
+use crate::geom::{dimension, Dimension, GetDimension, SetDimension};
+use crate::geom::{GetOrientation, GetPosition, SetOrientation, SetPosition};
+use crate::path::GetPath;
+
+use nannou::color::Rgb;
+use nannou::lyon::path::Path;
+
+/// Number animated like any other object, but never drawn, like manim's
+/// `ValueTracker`. Other objects follow it through updaters. Its position
+/// serves as a tracked point, moved with the usual actions.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ValueTracker {
+    value: f32,
+    position: geom::Point,
+    orientation: f32,
+    dimension: Dimension,
+    opacity: Opacity,
+}
+
+impl ValueTracker {
+    fn new(value: f32) -> Self {
+        ValueTracker {
+            value,
+            position: geom::point(),
+            orientation: 0.0,
+            dimension: dimension(0.0, 0.0),
+            opacity: Opacity::new(false),
+        }
+    }
+    pub fn value(&self) -> f32 {
+        self.value
+    }
+    pub fn set_value(&mut self, value: f32) {
+        self.value = value;
+    }
+}
+
+impl Draw for ValueTracker {
+    fn draw<B: Backend>(&self, _backend: &mut B) {}
+}
+
+impl GetPath for ValueTracker {
+    fn path(&self) -> Path {
+        Path::new()
+    }
+}
+
+impl PathCompletion for ValueTracker {
+    fn completion(&self) -> f32 {
+        1.0
+    }
+    fn set_completion(&mut self, _completion: f32) {}
+}
+
+impl SetPosition for ValueTracker {
+    fn position_mut(&mut self) -> &mut geom::Point {
+        SetPosition::position_mut(&mut self.position)
+    }
+}
+
+impl GetPosition for ValueTracker {
+    fn position(&self) -> geom::Point {
+        GetPosition::position(&self.position)
+    }
+}
+
+impl GetOrientation for ValueTracker {
+    fn orientation(&self) -> f32 {
+        self.orientation
+    }
+}
+
+impl SetOrientation for ValueTracker {
+    fn orientation_mut(&mut self) -> &mut f32 {
+        &mut self.orientation
+    }
+}
+
+impl GetDimension for ValueTracker {
+    fn dimension(&self) -> &Dimension {
+        GetDimension::dimension(&self.dimension)
+    }
+}
+
+impl SetDimension for ValueTracker {
+    fn dimension_mut(&mut self) -> &mut Dimension {
+        SetDimension::dimension_mut(&mut self.dimension)
+    }
+}
+
+impl GetColor for ValueTracker {
+    fn color(&self) -> Rgb {
+        DEFAULT_FILL_COLOR
+    }
+    fn stroke_color(&self) -> Rgb {
+        DEFAULT_STROKE_COLOR
+    }
+}
+
+impl SetColor for ValueTracker {
+    fn set_fill_paint(&mut self, _paint: Paint) {}
+    fn set_stroke_paint(&mut self, _paint: Paint) {}
+    fn set_stroke_weight(&mut self, _weight: f32) {}
+}
+
+impl GetOpacity for ValueTracker {
+    fn opacity(&self) -> f32 {
+        GetOpacity::opacity(&self.opacity)
+    }
+    fn is_visible(&self) -> bool {
+        GetOpacity::is_visible(&self.opacity)
+    }
+}
+
+impl SetOpacity for ValueTracker {
+    fn opacity_mut(&mut self) -> &mut Opacity {
+        SetOpacity::opacity_mut(&mut self.opacity)
+    }
+}
+
+pub fn value_tracker(value: f32) -> Object {
+    Object::new(ValueTracker::new(value).into())
+}
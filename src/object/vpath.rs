@@ -0,0 +1,363 @@
+use crate::animation::PathCompletion;
+use crate::appearance::{GetColor, GetOpacity, Opacity, Paint, SetColor, SetOpacity};
+use crate::arena::Object;
+use crate::consts::*;
+use crate::draw::{draw_outline, Backend, Draw, PathStyle, Transform};
+use crate::geom;
+use crate::geom::{dimension, Dimension, GetDimension, SetDimension};
+use crate::geom::{GetOrientation, GetPosition, SetOrientation, SetPosition};
+use crate::import::svg::Shape;
+use crate::path::{map_points, GetPath, MeasureCache};
+
+use crate::object::polygon::normalize;
+use nannou;
+use nannou::color::Rgb;
+
+use nannou::lyon::math as lyon;
+use nannou::lyon::path::builder::PathBuilder;
+use nannou::lyon::path::{Path, PathEvent};
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+enum Segment {
+    Move,
+    Line,
+    Quadratic,
+    Cubic,
+    Close,
+}
+
+/// Collects segments of a `VPath`, in coordinates of the scene.
+///
+/// ```ignore
+/// let path = VPath::builder()
+///     .move_to(point_at(0.0, 0.0))
+///     .quadratic_to(point_at(50.0, 100.0), point_at(100.0, 0.0))
+///     .close();
+/// let p = scene.path(path);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct VPathBuilder {
+    segments: Vec<Segment>,
+    points: Vec<geom::Point>,
+    filled: Option<bool>,
+    in_subpath: bool,
+}
+
+impl VPathBuilder {
+    pub fn new() -> Self {
+        Self {
+            segments: Vec::new(),
+            points: Vec::new(),
+            filled: None,
+            in_subpath: false,
+        }
+    }
+    pub fn move_to(mut self, to: geom::Point) -> Self {
+        self.push(Segment::Move, &[to]);
+        self.in_subpath = true;
+        self
+    }
+    pub fn line_to(self, to: geom::Point) -> Self {
+        self.segment(Segment::Line, &[to])
+    }
+    pub fn quadratic_to(self, ctrl: geom::Point, to: geom::Point) -> Self {
+        self.segment(Segment::Quadratic, &[ctrl, to])
+    }
+    pub fn cubic_to(self, ctrl1: geom::Point, ctrl2: geom::Point, to: geom::Point) -> Self {
+        self.segment(Segment::Cubic, &[ctrl1, ctrl2, to])
+    }
+    pub fn close(mut self) -> Self {
+        if self.in_subpath {
+            self.segments.push(Segment::Close);
+            self.in_subpath = false;
+        }
+        self
+    }
+    /// Whether to fill the path. By default only paths with a closed
+    /// sub-path are filled.
+    pub fn filled(mut self, filled: bool) -> Self {
+        self.filled = Some(filled);
+        self
+    }
+    // Segment without a sub-path to continue starts a new one at its end
+    fn segment(mut self, segment: Segment, points: &[geom::Point]) -> Self {
+        if self.in_subpath {
+            self.push(segment, points);
+            self
+        } else {
+            self.move_to(points[points.len() - 1])
+        }
+    }
+    fn push(&mut self, segment: Segment, points: &[geom::Point]) {
+        self.segments.push(segment);
+        self.points.extend_from_slice(points);
+    }
+    /// Segments of a lyon path, e.g. one imported from SVG
+    pub fn from_path(path: &Path) -> Self {
+        let at = |p: lyon::Point| geom::point_at(p.x, p.y);
+        path.iter().fold(Self::new(), |builder, event| match event {
+            PathEvent::Begin { at: to } => builder.move_to(at(to)),
+            PathEvent::Line { to, .. } => builder.line_to(at(to)),
+            PathEvent::Quadratic { ctrl, to, .. } => builder.quadratic_to(at(ctrl), at(to)),
+            PathEvent::Cubic {
+                ctrl1, ctrl2, to, ..
+            } => builder.cubic_to(at(ctrl1), at(ctrl2), at(to)),
+            PathEvent::End { close: true, .. } => builder.close(),
+            PathEvent::End { .. } => builder,
+        })
+    }
+    pub fn build(self) -> VPath {
+        VPath::new(self)
+    }
+}
+
+impl Default for VPathBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Arbitrary path of lines and Bezier curves, like manim's `VMobject`.
+/// Points are kept relative to the size of the path, so resizing stretches
+/// the shape like any other object.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VPath {
+    segments: Vec<Segment>,
+    points: Vec<lyon::Point>, // Within box of unit size around origin
+    filled: bool,
+    dimension: Dimension,
+    position: geom::Point,
+    orientation: f32,
+    path_completion: f32,
+    color: Paint,
+    stroke_color: Paint,
+    stroke_weight: f32,
+    opacity: Opacity,
+    measured: MeasureCache,
+}
+
+impl VPath {
+    pub fn builder() -> VPathBuilder {
+        VPathBuilder::new()
+    }
+    fn new(builder: VPathBuilder) -> Self {
+        let (position, dimension, points) = normalize(&builder.points);
+        let closed = builder.segments.contains(&Segment::Close);
+        VPath {
+            segments: builder.segments,
+            points,
+            filled: builder.filled.unwrap_or(closed),
+            dimension,
+            position,
+            orientation: 0.0,
+            path_completion: 1.0,
+            color: Paint::Solid(DEFAULT_FILL_COLOR),
+            stroke_color: Paint::Solid(DEFAULT_STROKE_COLOR),
+            stroke_weight: DEFAULT_STROKE_WEIGHT,
+            opacity: Opacity::new(false),
+            measured: MeasureCache::default(),
+        }
+    }
+    pub fn is_filled(&self) -> bool {
+        self.filled
+    }
+}
+
+impl GetPath for VPath {
+    fn path(&self) -> Path {
+        let (width, height) = (self.width(), self.height());
+        let mut points = self
+            .points
+            .iter()
+            .map(|p| lyon::point(p.x * width, p.y * height));
+        let mut next = || points.next().unwrap_or_else(|| lyon::point(0.0, 0.0));
+
+        let mut builder = Path::builder();
+        for segment in self.segments.iter() {
+            match segment {
+                Segment::Move => {
+                    builder.move_to(next());
+                }
+                Segment::Line => {
+                    builder.line_to(next());
+                }
+                Segment::Quadratic => {
+                    let ctrl = next();
+                    builder.quadratic_bezier_to(ctrl, next());
+                }
+                Segment::Cubic => {
+                    let (ctrl1, ctrl2) = (next(), next());
+                    builder.cubic_bezier_to(ctrl1, ctrl2, next());
+                }
+                Segment::Close => {
+                    builder.close();
+                }
+            }
+        }
+        builder.build()
+    }
+}
+
+impl Draw for VPath {
+    fn draw<B: Backend>(&self, backend: &mut B) {
+        if self.is_visible() {
+            let measured = self.measured.get(|| self.path());
+            let path = measured.upto(self.path_completion);
+            let transform = Transform::new(self.position, self.orientation);
+            let style = PathStyle::of(self).filled(self.filled);
+            draw_outline(backend, measured.path(), &path, &transform, style);
+        }
+    }
+}
+
+impl PathCompletion for VPath {
+    fn completion(&self) -> f32 {
+        self.path_completion
+    }
+    fn set_completion(&mut self, completion: f32) {
+        self.path_completion = completion.max(0.0).min(1.0);
+    }
+}
+
+impl SetPosition for VPath {
+    fn position_mut(&mut self) -> &mut geom::Point {
+        SetPosition::position_mut(&mut self.position)
+    }
+}
+
+impl GetPosition for VPath {
+    fn position(&self) -> geom::Point {
+        GetPosition::position(&self.position)
+    }
+}
+
+impl GetOrientation for VPath {
+    fn orientation(&self) -> f32 {
+        self.orientation
+    }
+}
+
+impl SetOrientation for VPath {
+    fn orientation_mut(&mut self) -> &mut f32 {
+        &mut self.orientation
+    }
+}
+
+impl GetDimension for VPath {
+    fn dimension(&self) -> &Dimension {
+        GetDimension::dimension(&self.dimension)
+    }
+}
+
+impl SetDimension for VPath {
+    fn dimension_mut(&mut self) -> &mut Dimension {
+        self.measured.clear();
+        SetDimension::dimension_mut(&mut self.dimension)
+    }
+}
+
+impl GetColor for VPath {
+    fn color(&self) -> Rgb {
+        self.color.color()
+    }
+    fn stroke_color(&self) -> Rgb {
+        self.stroke_color.color()
+    }
+    fn stroke_weight(&self) -> f32 {
+        self.stroke_weight
+    }
+    fn fill_paint(&self) -> Paint {
+        self.color.clone()
+    }
+    fn stroke_paint(&self) -> Paint {
+        self.stroke_color.clone()
+    }
+}
+
+impl SetColor for VPath {
+    fn set_fill_paint(&mut self, paint: Paint) {
+        self.color = paint;
+    }
+    fn set_stroke_paint(&mut self, paint: Paint) {
+        self.stroke_color = paint;
+    }
+    fn set_stroke_weight(&mut self, weight: f32) {
+        self.stroke_weight = weight.max(0.0);
+    }
+}
+
+impl GetOpacity for VPath {
+    fn opacity(&self) -> f32 {
+        GetOpacity::opacity(&self.opacity)
+    }
+    fn is_visible(&self) -> bool {
+        GetOpacity::is_visible(&self.opacity)
+    }
+}
+
+impl SetOpacity for VPath {
+    fn opacity_mut(&mut self) -> &mut Opacity {
+        SetOpacity::opacity_mut(&mut self.opacity)
+    }
+}
+
+pub fn vpath(builder: VPathBuilder) -> Object {
+    Object::new(builder.build().into())
+}
+
+/// Paths of the shapes of an SVG drawing, flipped to y up. Paths keep the
+/// fill, stroke and opacity set in the drawing, and are visible.
+pub fn svg_paths(shapes: &[Shape]) -> Vec<Object> {
+    shapes
+        .iter()
+        .map(|shape| {
+            let outline = map_points(&shape.path, |p| lyon::point(p.x, -p.y));
+            let style = &shape.style;
+            let mut path = VPathBuilder::from_path(&outline)
+                .filled(style.fill.is_some())
+                .build();
+            if let Some(fill) = style.fill {
+                path.set_color(fill);
+            }
+            match style.stroke {
+                Some(stroke) => {
+                    path.set_stroke_color(stroke);
+                    path.set_stroke_weight(style.stroke_width);
+                }
+                None => path.set_stroke_weight(0.0),
+            }
+            let opacity = if style.fill.is_some() {
+                style.fill_opacity
+            } else {
+                style.stroke_opacity
+            };
+            path.set_opacity(style.opacity * opacity);
+            Object::new(path.into())
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geom::point_at;
+    use crate::path::MeasureLength;
+
+    #[test]
+    fn build_and_resize() {
+        let mut path = VPath::builder()
+            .move_to(point_at(0.0, 0.0))
+            .line_to(point_at(100.0, 0.0))
+            .quadratic_to(point_at(100.0, 50.0), point_at(0.0, 50.0))
+            .close()
+            .build();
+        assert!(path.is_filled());
+        assert_eq!(path.position(), point_at(50.0, 25.0));
+        assert_eq!((path.width(), path.height()), (100.0, 50.0));
+
+        let length = path.path().approximate_length(0.01);
+        path.set_size(dimension(200.0, 100.0));
+        let scaled = path.path().approximate_length(0.01);
+        assert!((scaled - length * 2.0).abs() < 0.5);
+    }
+}
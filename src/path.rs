@@ -1,30 +1,27 @@
-use nannou::lyon::path::builder::FlatPathBuilder;
+use nannou::lyon::math::Point;
+use nannou::lyon::path::builder::{FlatPathBuilder, PathBuilder};
 use nannou::lyon::path::iterator::PathIterator;
 use nannou::lyon::path::Path;
 use nannou::lyon::path::PathEvent;
+
+use crate::consts::DEFAULT_FLATTEN_TOLERANCE;
+
+use std::cell::RefCell;
+use std::cmp::Ordering;
+use std::sync::Arc;
+
+/// Outline of an object in its local coordinates
+pub trait GetPath {
+    fn path(&self) -> Path;
+}
+
 pub trait MeasureLength {
     fn approximate_length(&self, tolerance: f32) -> f32;
 }
 
 impl MeasureLength for Path {
     fn approximate_length(&self, tolerance: f32) -> f32 {
-        let mut length = 0.0;
-        for e in self.iter().flattened(tolerance) {
-            match e {
-                PathEvent::Line { from, to } => {
-                    length += (to - from).length();
-                }
-                // PathEvent::End {
-                //     last,
-                //     first,
-                //     close: _,
-                // } => {
-                //     length += (last - first).length();
-                // }
-                _ => {}
-            }
-        }
-        length
+        Measured::new(self, tolerance).length()
     }
 }
 
@@ -37,51 +34,393 @@ impl GetPartial for Path {
         if ratio >= 1.0 {
             self.clone()
         } else {
-            let ratio = ratio.max(0.0);
-            let full_length = self.approximate_length(tolerance);
-            let stop_at = ratio * full_length;
+            Measured::new(self, tolerance).upto(ratio)
+        }
+    }
+}
 
-            let mut builder = Path::builder();
-            let mut length = 0.0;
+/// Path flattened into lines, with the distance along the path to every
+/// point, so that any part of it is drawn with a binary search
+#[derive(Debug, Clone)]
+pub struct Measured {
+    path: Path,
+    tolerance: f32,
+    points: Vec<Point>,
+    distances: Vec<f32>,          // Length of the path up to every point
+    subpaths: Vec<(usize, bool)>, // First point of every sub-path, and if it's closed
+}
 
-            for e in self.iter().flattened(tolerance) {
-                if length > stop_at {
-                    break;
+impl Measured {
+    pub fn new(path: &Path, tolerance: f32) -> Self {
+        let mut measured = Measured {
+            path: path.clone(),
+            tolerance,
+            points: Vec::new(),
+            distances: Vec::new(),
+            subpaths: Vec::new(),
+        };
+        let mut length = 0.0;
+        for e in path.iter().flattened(tolerance) {
+            match e {
+                PathEvent::Begin { at } => {
+                    measured.subpaths.push((measured.points.len(), false));
+                    measured.points.push(at);
+                    measured.distances.push(length);
                 }
-                match e {
-                    PathEvent::Begin { at } => {
-                        FlatPathBuilder::move_to(&mut builder, at);
-                    }
-                    PathEvent::Line { from, to } => {
-                        let seg_length = (to - from).length();
-                        let new_length = length + seg_length;
-                        if new_length > stop_at {
-                            let seg_ratio = 1.0 - (new_length - stop_at) / seg_length;
-                            FlatPathBuilder::line_to(&mut builder, from.lerp(to, seg_ratio));
-                            break;
-                        } else {
-                            length = new_length;
-                            FlatPathBuilder::line_to(&mut builder, to);
-                        }
-                    }
-                    PathEvent::End {
-                        last: _,
-                        first: _,
-                        close: _,
-                    } => {
-                        FlatPathBuilder::close(&mut builder);
+                PathEvent::Line { from, to } => {
+                    length += (to - from).length();
+                    measured.points.push(to);
+                    measured.distances.push(length);
+                }
+                PathEvent::End { close, .. } => {
+                    if let Some(subpath) = measured.subpaths.last_mut() {
+                        subpath.1 = close;
                     }
-                    _ => (),
                 }
+                _ => (),
+            }
+        }
+        measured
+    }
+    /// Path as measured, with its curves
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+    /// Length of the path, without closing segments
+    pub fn length(&self) -> f32 {
+        self.distances.last().cloned().unwrap_or(0.0)
+    }
+    /// Flattened path up to `ratio` of its length, or the whole path
+    pub fn upto(&self, ratio: f32) -> Path {
+        if ratio >= 1.0 {
+            return self.path.clone();
+        }
+        let stop_at = ratio.max(0.0) * self.length();
+        // First point beyond the part to draw
+        let cut = match self.distances.binary_search_by(|d| {
+            if *d <= stop_at {
+                Ordering::Less
+            } else {
+                Ordering::Greater
+            }
+        }) {
+            Ok(i) | Err(i) => i,
+        };
+
+        let mut builder = Path::builder();
+        for (i, (first, closed)) in self.subpaths.iter().enumerate() {
+            if *first >= cut {
+                break;
+            }
+            let end = self
+                .subpaths
+                .get(i + 1)
+                .map(|(next, _)| *next)
+                .unwrap_or_else(|| self.points.len());
+            FlatPathBuilder::move_to(&mut builder, self.points[*first]);
+            for p in self.points[*first + 1..end.min(cut)].iter() {
+                FlatPathBuilder::line_to(&mut builder, *p);
+            }
+            if cut < end {
+                let (from, to) = (self.points[cut - 1], self.points[cut]);
+                let span = self.distances[cut] - self.distances[cut - 1];
+                let t = (stop_at - self.distances[cut - 1]) / span;
+                FlatPathBuilder::line_to(&mut builder, from.lerp(to, t));
+                break;
+            }
+            if *closed {
+                FlatPathBuilder::close(&mut builder);
+            }
+        }
+        builder.build()
+    }
+}
+
+/// Part of every path drawn when they are drawn one after another, up to
+/// `completion` of their total length, like the glyphs of a text
+pub fn partials(paths: &[Measured], completion: f32) -> Vec<Path> {
+    let mut remaining = completion * paths.iter().map(Measured::length).sum::<f32>();
+    paths
+        .iter()
+        .map(|path| {
+            let length = path.length();
+            let part = if length > 0.0 {
+                (remaining / length).max(0.0).min(1.0)
+            } else {
+                1.0
+            };
+            remaining -= length;
+            path.upto(part)
+        })
+        .collect()
+}
+
+/// Measured outline kept by an object, so drawing part of it doesn't
+/// flatten it again every frame. Measured when first needed, and cleared by
+/// the object whenever its outline or size changes.
+#[derive(Debug, Clone, Default)]
+pub struct MeasureCache(RefCell<Option<Arc<Measured>>>);
+
+impl MeasureCache {
+    pub fn get<F: FnOnce() -> Path>(&self, outline: F) -> Arc<Measured> {
+        self.0
+            .borrow_mut()
+            .get_or_insert_with(|| Arc::new(Measured::new(&outline(), DEFAULT_FLATTEN_TOLERANCE)))
+            .clone()
+    }
+    pub fn clear(&mut self) {
+        *self.0.get_mut() = None;
+    }
+}
+
+// Objects are equal whether or not they were measured yet
+impl PartialEq for MeasureCache {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+/// Split path into its sub-paths, each starting with `Begin`
+pub fn subpaths(path: &Path) -> Vec<Vec<PathEvent>> {
+    let mut subpaths: Vec<Vec<PathEvent>> = Vec::new();
+    for e in path.iter() {
+        if let PathEvent::Begin { .. } = e {
+            subpaths.push(Vec::new());
+        }
+        if let Some(current) = subpaths.last_mut() {
+            current.push(e);
+        }
+    }
+    subpaths
+}
+
+/// Split path into outlines, e.g. one per glyph of a text.
+/// Sub-paths lying within the bounds of the preceding outer sub-path (like
+/// the hole of an 'o') are kept together with it, so outlines fill correctly.
+pub fn outlines(path: &Path) -> Vec<Path> {
+    let mut groups: Vec<(Bounds, Vec<PathEvent>)> = Vec::new();
+    for subpath in subpaths(path) {
+        let bounds = Bounds::of(&subpath);
+        match groups.last_mut() {
+            Some((outer, events)) if outer.contains(&bounds) => events.extend(subpath),
+            _ => groups.push((bounds, subpath)),
+        }
+    }
+    groups
+        .into_iter()
+        .map(|(_, events)| {
+            let mut builder = Path::builder();
+            for e in events {
+                builder.path_event(e);
             }
             builder.build()
+        })
+        .collect()
+}
+
+/// Flatten path into one closed polygon per sub-path
+pub fn polygons(path: &Path, tolerance: f32) -> Vec<Vec<Point>> {
+    let mut polygons: Vec<Vec<Point>> = Vec::new();
+    for e in path.iter().flattened(tolerance) {
+        match e {
+            PathEvent::Begin { at } => polygons.push(vec![at]),
+            PathEvent::Line { to, .. } => {
+                if let Some(polygon) = polygons.last_mut() {
+                    polygon.push(to);
+                }
+            }
+            PathEvent::End { first, .. } => {
+                // Closing point is implied
+                if let Some(polygon) = polygons.last_mut() {
+                    if polygon.len() > 1 && polygon.last() == Some(&first) {
+                        polygon.pop();
+                    }
+                }
+            }
+            _ => (),
+        }
+    }
+    polygons.retain(|polygon| !polygon.is_empty());
+    polygons
+}
+
+/// Resample closed polygon into `count` points, evenly spaced along it
+pub fn resample(polygon: &[Point], count: usize) -> Vec<Point> {
+    let n = polygon.len();
+    if n == 0 || count == 0 {
+        return Vec::new();
+    }
+    let edge = |i: usize| (polygon[(i + 1) % n] - polygon[i]).length();
+    let perimeter: f32 = (0..n).map(edge).sum();
+    if perimeter <= 0.0 {
+        return vec![polygon[0]; count];
+    }
+
+    let mut points = Vec::with_capacity(count);
+    let mut i = 0;
+    let mut edge_start = 0.0;
+    for k in 0..count {
+        let distance = perimeter * k as f32 / count as f32;
+        while i < n - 1 && edge_start + edge(i) < distance {
+            edge_start += edge(i);
+            i += 1;
+        }
+        let length = edge(i);
+        let t = if length > 0.0 {
+            ((distance - edge_start) / length).min(1.0)
+        } else {
+            0.0
+        };
+        points.push(polygon[i].lerp(polygon[(i + 1) % n], t));
+    }
+    points
+}
+
+/// Signed area of polygon, positive when counter-clockwise
+pub fn signed_area(polygon: &[Point]) -> f32 {
+    let n = polygon.len();
+    (0..n)
+        .map(|i| {
+            let (a, b) = (polygon[i], polygon[(i + 1) % n]);
+            a.x * b.y - b.x * a.y
+        })
+        .sum::<f32>()
+        / 2.0
+}
+
+/// Build path of closed polygons
+pub fn polygon_path(polygons: &[Vec<Point>]) -> Path {
+    let mut builder = Path::builder();
+    for polygon in polygons.iter().filter(|polygon| !polygon.is_empty()) {
+        builder.move_to(polygon[0]);
+        for p in polygon.iter().skip(1) {
+            builder.line_to(*p);
+        }
+        builder.close();
+    }
+    builder.build()
+}
+
+/// Copy of `path` with every point, including control points, mapped by `f`
+pub fn map_points<F: Fn(Point) -> Point>(path: &Path, f: F) -> Path {
+    let mut builder = Path::builder();
+    for event in path.iter() {
+        builder.path_event(match event {
+            PathEvent::Begin { at } => PathEvent::Begin { at: f(at) },
+            PathEvent::Line { from, to } => PathEvent::Line {
+                from: f(from),
+                to: f(to),
+            },
+            PathEvent::Quadratic { from, ctrl, to } => PathEvent::Quadratic {
+                from: f(from),
+                ctrl: f(ctrl),
+                to: f(to),
+            },
+            PathEvent::Cubic {
+                from,
+                ctrl1,
+                ctrl2,
+                to,
+            } => PathEvent::Cubic {
+                from: f(from),
+                ctrl1: f(ctrl1),
+                ctrl2: f(ctrl2),
+                to: f(to),
+            },
+            PathEvent::End { last, first, close } => PathEvent::End {
+                last: f(last),
+                first: f(first),
+                close,
+            },
+        });
+    }
+    builder.build()
+}
+
+/// Whether paths have the same events, as lyon paths can't be compared
+pub fn same_path(a: &Path, b: &Path) -> bool {
+    a.iter().eq(b.iter())
+}
+
+/// Join paths into one, keeping every subpath
+pub fn concat(paths: &[Path]) -> Path {
+    let mut builder = Path::builder();
+    for path in paths {
+        for event in path.iter() {
+            builder.path_event(event);
         }
     }
+    builder.build()
+}
+
+/// Axis-aligned bounds of end and control points
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Bounds {
+    pub min: Point,
+    pub max: Point,
+}
+
+impl Bounds {
+    pub fn of_path(path: &Path) -> Self {
+        Self::of(&path.iter().collect::<Vec<_>>())
+    }
+    pub fn of(events: &[PathEvent]) -> Self {
+        let mut bounds = Bounds {
+            min: Point::new(std::f32::MAX, std::f32::MAX),
+            max: Point::new(std::f32::MIN, std::f32::MIN),
+        };
+        for e in events {
+            match *e {
+                PathEvent::Begin { at } => bounds.include(at),
+                PathEvent::Line { to, .. } => bounds.include(to),
+                PathEvent::Quadratic { ctrl, to, .. } => {
+                    bounds.include(ctrl);
+                    bounds.include(to);
+                }
+                PathEvent::Cubic {
+                    ctrl1, ctrl2, to, ..
+                } => {
+                    bounds.include(ctrl1);
+                    bounds.include(ctrl2);
+                    bounds.include(to);
+                }
+                PathEvent::End { .. } => (),
+            }
+        }
+        bounds
+    }
+    /// Bounds of the flattened outline, without control points
+    #[cfg(test)]
+    pub fn of_outline(path: &Path) -> Self {
+        let mut bounds = Self::of(&[]);
+        for p in polygons(path, crate::consts::DEFAULT_FLATTEN_TOLERANCE).concat() {
+            bounds.include(p);
+        }
+        bounds
+    }
+    pub fn is_empty(&self) -> bool {
+        self.min.x > self.max.x || self.min.y > self.max.y
+    }
+    pub fn center(&self) -> Point {
+        self.min.lerp(self.max, 0.5)
+    }
+    fn include(&mut self, p: Point) {
+        self.min = Point::new(self.min.x.min(p.x), self.min.y.min(p.y));
+        self.max = Point::new(self.max.x.max(p.x), self.max.y.max(p.y));
+    }
+    fn contains(&self, other: &Bounds) -> bool {
+        self.min.x <= other.min.x
+            && self.min.y <= other.min.y
+            && self.max.x >= other.max.x
+            && self.max.y >= other.max.y
+    }
 }
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use nannou::geom::rect::Rect;
+    use nannou::lyon::math::point;
     use nannou::lyon::path::builder::PathBuilder;
     use nannou::prelude::*;
     #[test]
@@ -98,4 +437,46 @@ mod tests {
 
         println!("length = {}", partial_path.approximate_length(0.01));
     }
+
+    #[test]
+    fn measured_partial_path() {
+        let mut builder = Path::builder();
+        builder.move_to(point(0.0, 0.0));
+        builder.line_to(point(10.0, 0.0));
+        builder.line_to(point(10.0, 10.0));
+        builder.close();
+        builder.move_to(point(20.0, 0.0));
+        builder.line_to(point(40.0, 0.0));
+        let path = builder.build();
+
+        let measured = Measured::new(&path, 0.01);
+        assert_eq!(measured.length(), 40.0);
+        assert!(same_path(&measured.upto(1.0), &path));
+
+        // First sub-path is complete and closed, second one cut halfway
+        let half = path.upto(0.75, 0.01);
+        let events: Vec<_> = half.iter().collect();
+        assert_eq!(events.len(), 7);
+        assert_eq!(Bounds::of_path(&half).max, point(30.0, 10.0));
+        assert!(path.upto(0.0, 0.01).approximate_length(0.01) == 0.0);
+    }
+
+    #[test]
+    fn cache_is_kept_until_cleared() {
+        let square = |size: f32| {
+            polygon_path(&[vec![
+                point(0.0, 0.0),
+                point(size, 0.0),
+                point(size, size),
+                point(0.0, size),
+            ]])
+        };
+        let mut cache = MeasureCache::default();
+        let measured = cache.get(|| square(10.0));
+        assert!(Arc::ptr_eq(&measured, &cache.get(|| square(20.0))));
+        assert_eq!(cache.clone(), MeasureCache::default());
+
+        cache.clear();
+        assert_eq!(cache.get(|| square(20.0)).length(), 60.0);
+    }
 }
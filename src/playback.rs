@@ -0,0 +1,203 @@
+//! Playback state of the preview window: pause, stepping, speed, scrubbing
+//! and looping. Time here is scene time, which is handed to `Scene::update`.
+
+const MIN_SPEED: f32 = 0.125;
+const MAX_SPEED: f32 = 8.0;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Playback {
+    time: f32,
+    start: f32,
+    end: f32,
+    speed: f32,
+    paused: bool,
+    looping: bool,
+    loop_in: Option<f32>,
+    loop_out: Option<f32>,
+}
+
+impl Playback {
+    /// Playback between `start` and `end` seconds, starting unpaused at `start`
+    pub fn new(start: f32, end: f32) -> Self {
+        Self {
+            time: start,
+            start,
+            end: end.max(start),
+            speed: 1.0,
+            paused: false,
+            looping: false,
+            loop_in: None,
+            loop_out: None,
+        }
+    }
+    pub fn time(&self) -> f32 {
+        self.time
+    }
+    pub fn start(&self) -> f32 {
+        self.start
+    }
+    pub fn end(&self) -> f32 {
+        self.end
+    }
+    pub fn speed(&self) -> f32 {
+        self.speed
+    }
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+    pub fn is_looping(&self) -> bool {
+        self.looping
+    }
+    /// Section that is looped, whole range unless in/out points are set
+    pub fn loop_range(&self) -> (f32, f32) {
+        let loop_in = self.loop_in.unwrap_or(self.start);
+        let loop_out = self.loop_out.unwrap_or(self.end);
+        (loop_in.min(loop_out), loop_in.max(loop_out))
+    }
+    /// Progress playback by `dt` seconds of wall-clock time
+    pub fn advance(&mut self, dt: f32) {
+        if self.paused {
+            return;
+        }
+        let time = self.time + dt * self.speed;
+        if self.looping {
+            let (loop_in, loop_out) = self.loop_range();
+            let length = loop_out - loop_in;
+            if time > loop_out && length > 0.0 {
+                self.time = loop_in + (time - loop_in) % length;
+                return;
+            }
+        }
+        self.time = time.min(self.end);
+    }
+    pub fn toggle_pause(&mut self) {
+        self.paused = !self.paused;
+    }
+    /// Pause and move by `frames` frames, backwards if negative
+    pub fn step(&mut self, frames: i32, fps: f32) {
+        self.paused = true;
+        self.seek(self.time + frames as f32 / fps);
+    }
+    pub fn seek(&mut self, time: f32) {
+        self.time = time.max(self.start).min(self.end);
+    }
+    /// Seek to the position `ratio` (0 to 1) of the whole range
+    pub fn scrub(&mut self, ratio: f32) {
+        let ratio = ratio.max(0.0).min(1.0);
+        self.seek(self.start + (self.end - self.start) * ratio);
+    }
+    /// Jump to first cut point after current time
+    pub fn next_cut(&mut self, cuts: &[f32]) {
+        let time = self.time;
+        if let Some(cut) = cuts.iter().cloned().find(|cut| *cut > time + 1e-4) {
+            self.seek(cut);
+        } else {
+            self.seek(self.end);
+        }
+    }
+    /// Jump to last cut point before current time
+    pub fn previous_cut(&mut self, cuts: &[f32]) {
+        let time = self.time;
+        if let Some(cut) = cuts.iter().cloned().rev().find(|cut| *cut < time - 1e-4) {
+            self.seek(cut);
+        } else {
+            self.seek(self.start);
+        }
+    }
+    pub fn faster(&mut self) {
+        self.speed = (self.speed * 2.0).min(MAX_SPEED);
+    }
+    pub fn slower(&mut self) {
+        self.speed = (self.speed / 2.0).max(MIN_SPEED);
+    }
+    pub fn reset_speed(&mut self) {
+        self.speed = 1.0;
+    }
+    pub fn set_loop_in(&mut self) {
+        self.loop_in = Some(self.time);
+    }
+    pub fn set_loop_out(&mut self) {
+        self.loop_out = Some(self.time);
+    }
+    pub fn clear_loop(&mut self) {
+        self.loop_in = None;
+        self.loop_out = None;
+    }
+    pub fn toggle_loop(&mut self) {
+        self.looping = !self.looping;
+    }
+    pub fn restart(&mut self) {
+        self.time = if self.looping {
+            self.loop_range().0
+        } else {
+            self.start
+        };
+    }
+}
+
+/// Unique, sorted cut points from command time stamps
+pub fn cut_points(time_stamps: &[f32]) -> Vec<f32> {
+    let mut cuts: Vec<f32> = time_stamps.to_vec();
+    cuts.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    cuts.dedup();
+    cuts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pause_and_speed() {
+        let mut playback = Playback::new(0.0, 4.0);
+        playback.advance(1.0);
+        assert_eq!(playback.time(), 1.0);
+
+        playback.faster();
+        playback.advance(1.0);
+        assert_eq!(playback.time(), 3.0);
+
+        playback.toggle_pause();
+        playback.advance(1.0);
+        assert_eq!(playback.time(), 3.0);
+
+        playback.toggle_pause();
+        playback.advance(5.0);
+        assert_eq!(playback.time(), 4.0);
+    }
+
+    #[test]
+    fn loop_section() {
+        let mut playback = Playback::new(0.0, 10.0);
+        playback.seek(2.0);
+        playback.set_loop_in();
+        playback.seek(4.0);
+        playback.set_loop_out();
+        playback.toggle_loop();
+
+        playback.seek(3.5);
+        playback.advance(1.0);
+        assert_eq!(playback.time(), 2.5);
+    }
+
+    #[test]
+    fn jump_between_cuts() {
+        let cuts = cut_points(&[0.0, 0.0, 1.0, 1.0, 2.5]);
+        assert_eq!(cuts, vec![0.0, 1.0, 2.5]);
+
+        let mut playback = Playback::new(0.0, 3.0);
+        playback.seek(0.5);
+        playback.next_cut(&cuts);
+        assert_eq!(playback.time(), 1.0);
+        playback.next_cut(&cuts);
+        assert_eq!(playback.time(), 2.5);
+        playback.next_cut(&cuts);
+        assert_eq!(playback.time(), 3.0);
+        playback.previous_cut(&cuts);
+        assert_eq!(playback.time(), 2.5);
+
+        playback.step(-1, 2.0);
+        assert!(playback.is_paused());
+        assert_eq!(playback.time(), 2.0);
+    }
+}
@@ -0,0 +1,305 @@
+use crate::appearance::Paint;
+use crate::consts::*;
+use crate::draw::{Backend, Transform};
+
+use nannou::color::{Rgb, Rgba};
+use nannou::image::{Rgba as Pixel, RgbaImage};
+use nannou::lyon::math as lyon;
+use nannou::lyon::path::Path;
+use nannou::lyon::tessellation::{
+    BuffersBuilder, FillAttributes, FillOptions, FillTessellator, StrokeAttributes, StrokeOptions,
+    StrokeTessellator, VertexBuffers,
+};
+
+type Geometry = VertexBuffers<lyon::Point, u32>;
+
+// Color of drawn triangles, either one color or sampled from a paint at
+// every subpixel. Colors are premultiplied.
+enum Shade<'a> {
+    Flat([f32; 4]),
+    Paint(&'a Paint, f32),
+}
+
+impl<'a> Shade<'a> {
+    fn at(&self, p: lyon::Point) -> [f32; 4] {
+        match *self {
+            Shade::Flat(src) => src,
+            Shade::Paint(paint, alpha) => premultiply(paint.at(p), alpha),
+        }
+    }
+}
+
+/// CPU rasterizer used for offline rendering.
+///
+/// Paths are tessellated with lyon (same as nannou does for the window),
+/// and the resulting triangles are filled into a supersampled buffer of
+/// premultiplied colors which is box-filtered down on `to_image`.
+/// Coordinates follow nannou: origin at the center, y pointing up.
+pub struct Canvas {
+    width: u32,
+    height: u32,
+    samples: u32,
+    buffer: Vec<[f32; 4]>,
+    fill_tessellator: FillTessellator,
+    stroke_tessellator: StrokeTessellator,
+}
+
+impl Canvas {
+    pub fn new(width: u32, height: u32) -> Self {
+        Self::with_samples(width, height, DEFAULT_SUPERSAMPLING)
+    }
+    /// Create canvas with `samples` x `samples` subpixels per pixel
+    pub fn with_samples(width: u32, height: u32, samples: u32) -> Self {
+        let samples = samples.max(1);
+        let len = (width * samples * height * samples) as usize;
+        Self {
+            width,
+            height,
+            samples,
+            buffer: vec![[0.0; 4]; len],
+            fill_tessellator: FillTessellator::new(),
+            stroke_tessellator: StrokeTessellator::new(),
+        }
+    }
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+    pub fn clear(&mut self, color: Rgb) {
+        let color = [color.red, color.green, color.blue, 1.0];
+        for sample in self.buffer.iter_mut() {
+            *sample = color;
+        }
+    }
+    /// Resolve the supersampled buffer into an 8-bit image
+    pub fn to_image(&self) -> RgbaImage {
+        let samples = self.samples as usize;
+        let stride = self.width as usize * samples;
+        let count = (samples * samples) as f32;
+
+        let mut image = RgbaImage::new(self.width, self.height);
+        for (x, y, pixel) in image.enumerate_pixels_mut() {
+            let mut sum = [0.0; 4];
+            for sy in 0..samples {
+                let row = (y as usize * samples + sy) * stride;
+                for sx in 0..samples {
+                    let sample = self.buffer[row + x as usize * samples + sx];
+                    for c in 0..4 {
+                        sum[c] += sample[c];
+                    }
+                }
+            }
+            let alpha = sum[3] / count;
+            let unmultiply = |c: f32| {
+                if alpha > 0.0 {
+                    c / count / alpha
+                } else {
+                    0.0
+                }
+            };
+            *pixel = Pixel([
+                to_u8(unmultiply(sum[0])),
+                to_u8(unmultiply(sum[1])),
+                to_u8(unmultiply(sum[2])),
+                to_u8(alpha),
+            ]);
+        }
+        image
+    }
+    // Scene coordinates to supersampled buffer coordinates
+    fn to_buffer(&self, p: lyon::Point) -> [f32; 2] {
+        let s = self.samples as f32;
+        [
+            (p.x + self.width as f32 / 2.0) * s,
+            (self.height as f32 / 2.0 - p.y) * s,
+        ]
+    }
+    fn draw_geometry(&mut self, geometry: &Geometry, transform: &Transform, shade: Shade) {
+        let points: Vec<[f32; 2]> = geometry
+            .vertices
+            .iter()
+            .map(|p| self.to_buffer(transform.apply(*p)))
+            .collect();
+        for tri in geometry.indices.chunks_exact(3) {
+            let (a, b, c) = (tri[0] as usize, tri[1] as usize, tri[2] as usize);
+            let local = [
+                geometry.vertices[a],
+                geometry.vertices[b],
+                geometry.vertices[c],
+            ];
+            self.fill_triangle(points[a], points[b], points[c], local, &shade);
+        }
+    }
+    // `local` are the corners in object coordinates, where paints are sampled
+    fn fill_triangle(
+        &mut self,
+        a: [f32; 2],
+        b: [f32; 2],
+        c: [f32; 2],
+        local: [lyon::Point; 3],
+        shade: &Shade,
+    ) {
+        let area = edge(a, b, c);
+        if area.abs() < std::f32::EPSILON {
+            return;
+        }
+        let buffer_w = (self.width * self.samples) as f32;
+        let buffer_h = (self.height * self.samples) as f32;
+        let min_x = a[0].min(b[0]).min(c[0]).floor().max(0.0) as usize;
+        let min_y = a[1].min(b[1]).min(c[1]).floor().max(0.0) as usize;
+        let max_x = a[0].max(b[0]).max(c[0]).ceil().min(buffer_w) as usize;
+        let max_y = a[1].max(b[1]).max(c[1]).ceil().min(buffer_h) as usize;
+        let stride = buffer_w as usize;
+
+        for y in min_y..max_y {
+            for x in min_x..max_x {
+                let p = [x as f32 + 0.5, y as f32 + 0.5];
+                // Barycentric weights share the sign of the triangle area when inside
+                let w0 = edge(b, c, p) * area.signum();
+                let w1 = edge(c, a, p) * area.signum();
+                let w2 = edge(a, b, p) * area.signum();
+                if w0 >= 0.0 && w1 >= 0.0 && w2 >= 0.0 {
+                    let src = match shade {
+                        Shade::Flat(src) => *src,
+                        _ => {
+                            let sum = w0 + w1 + w2;
+                            let p = (local[0].to_vector() * w0
+                                + local[1].to_vector() * w1
+                                + local[2].to_vector() * w2)
+                                / sum;
+                            shade.at(p.to_point())
+                        }
+                    };
+                    let dst = &mut self.buffer[y * stride + x];
+                    for i in 0..4 {
+                        dst[i] = src[i] + dst[i] * (1.0 - src[3]);
+                    }
+                }
+            }
+        }
+    }
+    fn fill_geometry(&mut self, path: &Path) -> Option<Geometry> {
+        let mut geometry: Geometry = VertexBuffers::new();
+        let options = FillOptions::tolerance(DEFAULT_FLATTEN_TOLERANCE);
+        self.fill_tessellator
+            .tessellate_path(
+                path,
+                &options,
+                &mut BuffersBuilder::new(&mut geometry, |p: lyon::Point, _: FillAttributes| p),
+            )
+            .ok()
+            .map(|_| geometry)
+    }
+    fn stroke_geometry(&mut self, path: &Path, weight: f32) -> Option<Geometry> {
+        let mut geometry: Geometry = VertexBuffers::new();
+        let options = StrokeOptions::tolerance(DEFAULT_FLATTEN_TOLERANCE).with_line_width(weight);
+        self.stroke_tessellator
+            .tessellate_path(
+                path,
+                &options,
+                &mut BuffersBuilder::new(&mut geometry, |p: lyon::Point, _: StrokeAttributes| p),
+            )
+            .ok()
+            .map(|_| geometry)
+    }
+}
+
+impl Backend for Canvas {
+    fn fill(&mut self, path: &Path, transform: &Transform, color: Rgba) {
+        self.fill_paint(path, transform, &Paint::Solid(color.color), color.alpha);
+    }
+    fn stroke(&mut self, path: &Path, transform: &Transform, color: Rgba, weight: f32) {
+        self.stroke_paint(
+            path,
+            transform,
+            &Paint::Solid(color.color),
+            color.alpha,
+            weight,
+        );
+    }
+    fn fill_paint(&mut self, path: &Path, transform: &Transform, paint: &Paint, alpha: f32) {
+        let alpha = alpha.max(0.0).min(1.0);
+        if alpha <= 0.0 {
+            return;
+        }
+        if let Some(geometry) = self.fill_geometry(path) {
+            self.draw_geometry(&geometry, transform, shade(paint, alpha));
+        }
+    }
+    fn stroke_paint(
+        &mut self,
+        path: &Path,
+        transform: &Transform,
+        paint: &Paint,
+        alpha: f32,
+        weight: f32,
+    ) {
+        let alpha = alpha.max(0.0).min(1.0);
+        if alpha <= 0.0 {
+            return;
+        }
+        if let Some(geometry) = self.stroke_geometry(path, weight) {
+            self.draw_geometry(&geometry, transform, shade(paint, alpha));
+        }
+    }
+}
+
+fn shade(paint: &Paint, alpha: f32) -> Shade {
+    match paint {
+        Paint::Solid(color) => Shade::Flat(premultiply(*color, alpha)),
+        Paint::Gradient(_) => Shade::Paint(paint, alpha),
+    }
+}
+
+fn premultiply(color: Rgb, alpha: f32) -> [f32; 4] {
+    [
+        color.red * alpha,
+        color.green * alpha,
+        color.blue * alpha,
+        alpha,
+    ]
+}
+
+// Twice the signed area of triangle (a, b, p)
+fn edge(a: [f32; 2], b: [f32; 2], p: [f32; 2]) -> f32 {
+    (b[0] - a[0]) * (p[1] - a[1]) - (b[1] - a[1]) * (p[0] - a[0])
+}
+
+fn to_u8(c: f32) -> u8 {
+    (c.max(0.0).min(1.0) * 255.0).round() as u8
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geom::point_at;
+    use nannou::lyon::math::point;
+
+    #[test]
+    fn fill_square() {
+        let mut canvas = Canvas::new(40, 40);
+        canvas.clear(BLACK);
+
+        let mut builder = Path::builder();
+        builder.move_to(point(-10.0, 10.0));
+        builder.line_to(point(10.0, 10.0));
+        builder.line_to(point(10.0, -10.0));
+        builder.line_to(point(-10.0, -10.0));
+        builder.close();
+        let path = builder.build();
+
+        let color = Rgba {
+            color: WHITE,
+            alpha: 1.0,
+        };
+        canvas.fill(&path, &Transform::new(point_at(5.0, 0.0), 0.0), color);
+        let image = canvas.to_image();
+
+        // Center of the square (shifted right by 5)
+        assert_eq!(image.get_pixel(25, 20), &Pixel([255, 255, 255, 255]));
+        // Outside of the square stays background
+        assert_eq!(image.get_pixel(2, 20), &Pixel([0, 0, 0, 255]));
+    }
+}
@@ -0,0 +1,147 @@
+//! Offline rendering of a `Scene`, without a window or GPU.
+//!
+//! The scene is stepped at a fixed frame rate from time 0 to the end of its
+//! commands, each frame is rasterized on the CPU by `Canvas`, and the result
+//! is handed to a `FrameSink` (e.g. `PngSequence` or `VideoEncoder`).
+//! Frames can also be exported as vector art through `SvgDocument`.
+pub use self::canvas::Canvas;
+pub use self::png::PngSequence;
+pub use self::svg::SvgDocument;
+pub use self::video::VideoEncoder;
+
+pub mod canvas;
+pub mod png;
+pub mod svg;
+pub mod video;
+
+use crate::animation::RunCommand;
+use crate::consts::*;
+use crate::scene::Scene;
+
+use nannou::color::Rgb;
+use nannou::image::RgbaImage;
+
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// Receives rendered frames in order
+pub trait FrameSink {
+    fn write_frame(&mut self, index: usize, frame: &RgbaImage) -> io::Result<()>;
+    fn finish(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Resolution, frame rate, time range and background used for offline rendering
+#[derive(Debug, Clone, PartialEq)]
+pub struct RenderSettings {
+    pub width: u32,
+    pub height: u32,
+    pub fps: f32,
+    pub background: Rgb,
+    pub start: f32,
+    pub end: Option<f32>, // End of the scene if `None`
+}
+
+impl RenderSettings {
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            fps: DEFAULT_FPS,
+            background: BLACK,
+            start: 0.0,
+            end: None,
+        }
+    }
+    pub fn fps(mut self, fps: f32) -> Self {
+        self.fps = fps;
+        self
+    }
+    pub fn background(mut self, color: Rgb) -> Self {
+        self.background = color;
+        self
+    }
+    /// Only render between `start` and `end` seconds
+    pub fn range(mut self, start: f32, end: Option<f32>) -> Self {
+        self.start = start.max(0.0);
+        self.end = end;
+        self
+    }
+    pub fn window(&self) -> nannou::geom::Rect {
+        nannou::geom::Rect::from_w_h(self.width as f32, self.height as f32)
+    }
+    /// Number of frames needed to cover `duration` seconds, including both ends
+    pub fn frame_count(&self, duration: f32) -> usize {
+        (duration.max(0.0) * self.fps).ceil() as usize + 1
+    }
+}
+
+/// Step `scene` through every frame time, calling `f` with the frame index
+/// after each update. Returns the number of frames visited.
+pub fn for_each_frame<F>(
+    scene: &mut Scene,
+    settings: &RenderSettings,
+    mut f: F,
+) -> io::Result<usize>
+where
+    F: FnMut(usize, &Scene) -> io::Result<()>,
+{
+    let end = settings.end.unwrap_or_else(|| scene.commands.end_time());
+    let frames = settings.frame_count(end - settings.start);
+    for index in 0..frames {
+        let time = settings.start + index as f32 / settings.fps;
+        scene.update(time);
+        f(index, scene)?;
+    }
+    Ok(frames)
+}
+
+/// Render every frame of `scene` into `sink`. Returns the number of frames written.
+pub fn render<S: FrameSink>(
+    scene: &mut Scene,
+    settings: &RenderSettings,
+    sink: &mut S,
+) -> io::Result<usize> {
+    let mut canvas = Canvas::new(settings.width, settings.height);
+    let frames = for_each_frame(scene, settings, |index, scene| {
+        canvas.clear(settings.background);
+        scene.draw(&mut canvas);
+        sink.write_frame(index, &canvas.to_image())
+    })?;
+    sink.finish()?;
+    Ok(frames)
+}
+
+/// Draw the current state of `scene` as an SVG document
+pub fn svg_document(scene: &Scene, settings: &RenderSettings) -> SvgDocument {
+    let mut document =
+        SvgDocument::new(settings.width, settings.height).background(settings.background);
+    scene.draw(&mut document);
+    document
+}
+
+/// Export the frame of `scene` at `time` as a single SVG file
+pub fn svg_frame<P: AsRef<Path>>(
+    scene: &mut Scene,
+    settings: &RenderSettings,
+    time: f32,
+    path: P,
+) -> io::Result<()> {
+    scene.update(time);
+    svg_document(scene, settings).save(path)
+}
+
+/// Export every frame of `scene` as numbered SVG files in `directory`
+pub fn svg_sequence<P: AsRef<Path>>(
+    scene: &mut Scene,
+    settings: &RenderSettings,
+    directory: P,
+) -> io::Result<usize> {
+    let directory = directory.as_ref();
+    fs::create_dir_all(directory)?;
+    for_each_frame(scene, settings, |index, scene| {
+        svg_document(scene, settings).save(directory.join(format!("frame_{:05}.svg", index)))
+    })
+}
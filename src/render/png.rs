@@ -0,0 +1,40 @@
+use super::FrameSink;
+
+use nannou::image::RgbaImage;
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Writes each frame as a numbered PNG file, e.g. `out/frame_00042.png`
+pub struct PngSequence {
+    directory: PathBuf,
+    prefix: String,
+}
+
+impl PngSequence {
+    /// Create sink writing into `directory`, which is created if missing
+    pub fn new<P: AsRef<Path>>(directory: P) -> io::Result<Self> {
+        fs::create_dir_all(directory.as_ref())?;
+        Ok(Self {
+            directory: directory.as_ref().to_path_buf(),
+            prefix: String::from("frame_"),
+        })
+    }
+    pub fn prefix(mut self, prefix: &str) -> Self {
+        self.prefix = String::from(prefix);
+        self
+    }
+    pub fn frame_path(&self, index: usize) -> PathBuf {
+        self.directory
+            .join(format!("{}{:05}.png", self.prefix, index))
+    }
+}
+
+impl FrameSink for PngSequence {
+    fn write_frame(&mut self, index: usize, frame: &RgbaImage) -> io::Result<()> {
+        frame
+            .save(self.frame_path(index))
+            .map_err(|e| io::Error::new(io::ErrorKind::Other, e))
+    }
+}
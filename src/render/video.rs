@@ -0,0 +1,209 @@
+use super::{FrameSink, RenderSettings};
+
+use nannou::image::RgbaImage;
+
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Stdio};
+
+/// Streams raw RGBA frames to an external encoder through its stdin.
+///
+/// By default this runs `ffmpeg`, picking a codec from the output extension
+/// (`libvpx-vp9` for `.webm`, `libx264` otherwise). The encoder process is
+/// spawned on the first frame and waited on in `finish`. If writing fails
+/// or the encoder is dropped before `finish`, the process is killed.
+pub struct VideoEncoder {
+    output: PathBuf,
+    width: u32,
+    height: u32,
+    fps: f32,
+    program: String,
+    codec: String,
+    pixel_format: String,
+    arguments: Option<Vec<String>>,
+    child: Option<Child>,
+}
+
+impl VideoEncoder {
+    pub fn new<P: AsRef<Path>>(output: P, settings: &RenderSettings) -> Self {
+        let output = output.as_ref().to_path_buf();
+        let codec = match output.extension().and_then(|ext| ext.to_str()) {
+            Some("webm") => "libvpx-vp9",
+            _ => "libx264",
+        };
+        Self {
+            output,
+            width: settings.width,
+            height: settings.height,
+            fps: settings.fps,
+            program: String::from("ffmpeg"),
+            codec: String::from(codec),
+            pixel_format: String::from("yuv420p"),
+            arguments: None,
+            child: None,
+        }
+    }
+    /// Encoder executable, `ffmpeg` by default
+    pub fn program(mut self, program: &str) -> Self {
+        self.program = String::from(program);
+        self
+    }
+    pub fn codec(mut self, codec: &str) -> Self {
+        self.codec = String::from(codec);
+        self
+    }
+    /// Pixel format of the encoded video, `yuv420p` by default
+    pub fn pixel_format(mut self, pixel_format: &str) -> Self {
+        self.pixel_format = String::from(pixel_format);
+        self
+    }
+    /// Replace the default ffmpeg arguments entirely. The program must
+    /// read `width` x `height` RGBA frames from stdin.
+    pub fn arguments(mut self, arguments: &[&str]) -> Self {
+        self.arguments = Some(arguments.iter().map(|arg| String::from(*arg)).collect());
+        self
+    }
+    pub fn output(&self) -> &Path {
+        &self.output
+    }
+    fn default_arguments(&self) -> Vec<String> {
+        let size = format!("{}x{}", self.width, self.height);
+        let fps = format!("{}", self.fps);
+        let output = self.output.to_string_lossy().into_owned();
+        vec![
+            "-y",
+            "-loglevel",
+            "error",
+            "-f",
+            "rawvideo",
+            "-pix_fmt",
+            "rgba",
+            "-s",
+            size.as_str(),
+            "-r",
+            fps.as_str(),
+            "-i",
+            "-",
+            "-an",
+            "-c:v",
+            self.codec.as_str(),
+            "-pix_fmt",
+            self.pixel_format.as_str(),
+            output.as_str(),
+        ]
+        .into_iter()
+        .map(String::from)
+        .collect()
+    }
+    fn spawn(&mut self) -> io::Result<&mut Child> {
+        if self.child.is_none() {
+            let arguments = match &self.arguments {
+                Some(arguments) => arguments.clone(),
+                None => self.default_arguments(),
+            };
+            let child = Command::new(&self.program)
+                .args(&arguments)
+                .stdin(Stdio::piped())
+                .spawn()?;
+            self.child = Some(child);
+        }
+        Ok(self.child.as_mut().unwrap())
+    }
+    // Stop the encoder without waiting for it to finish the video
+    fn abort(&mut self) {
+        if let Some(mut child) = self.child.take() {
+            drop(child.stdin.take());
+            let _ = child.kill();
+            let _ = child.wait();
+        }
+    }
+}
+
+impl FrameSink for VideoEncoder {
+    fn write_frame(&mut self, _index: usize, frame: &RgbaImage) -> io::Result<()> {
+        if frame.width() != self.width || frame.height() != self.height {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "Frame is {}x{}, but encoder expects {}x{}",
+                    frame.width(),
+                    frame.height(),
+                    self.width,
+                    self.height
+                ),
+            ));
+        }
+        let child = self.spawn()?;
+        let written = match child.stdin.as_mut() {
+            Some(stdin) => stdin.write_all(frame.as_raw()),
+            None => Err(io::Error::new(
+                io::ErrorKind::BrokenPipe,
+                "Encoder stdin is closed",
+            )),
+        };
+        if written.is_err() {
+            self.abort();
+        }
+        written
+    }
+    fn finish(&mut self) -> io::Result<()> {
+        if let Some(mut child) = self.child.take() {
+            // Closing stdin signals the end of the stream
+            drop(child.stdin.take());
+            let status = child.wait()?;
+            if !status.success() {
+                return Err(io::Error::new(
+                    io::ErrorKind::Other,
+                    format!("{} exited with {}", self.program, status),
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Drop for VideoEncoder {
+    fn drop(&mut self) {
+        self.abort();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arguments_follow_settings() {
+        let settings = RenderSettings::new(320, 240).fps(24.0);
+        let encoder = VideoEncoder::new("out.webm", &settings);
+        let arguments = encoder.default_arguments();
+        let after = |flag: &str| {
+            let index = arguments.iter().rposition(|arg| arg == flag).unwrap();
+            arguments[index + 1].as_str()
+        };
+        assert_eq!(after("-s"), "320x240");
+        assert_eq!(after("-r"), "24");
+        assert_eq!(after("-c:v"), "libvpx-vp9");
+        assert_eq!(after("-pix_fmt"), "yuv420p");
+        assert_eq!(arguments.last().unwrap(), "out.webm");
+
+        let encoder = VideoEncoder::new("out.mp4", &settings).codec("libx265");
+        assert!(encoder
+            .default_arguments()
+            .contains(&String::from("libx265")));
+    }
+
+    #[test]
+    fn missing_encoder_is_an_error() {
+        let settings = RenderSettings::new(4, 4);
+        let mut encoder = VideoEncoder::new("out.mp4", &settings).program("no-such-encoder");
+        let error = encoder.write_frame(0, &RgbaImage::new(4, 4)).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::NotFound);
+        assert!(encoder.child.is_none());
+
+        // Frames of the wrong size are refused before spawning anything
+        let error = encoder.write_frame(0, &RgbaImage::new(2, 2)).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+        assert!(encoder.finish().is_ok());
+    }
+}
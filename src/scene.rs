@@ -1,23 +1,45 @@
-use crate::animation::{AnimBuilder, RunCommand, TargetAction, TimedCommand, UserCommand};
-use crate::arena::{AddObject, Arena, CircleId, HasArena, Id, Index};
-use crate::arena::{NodeArena, NodeIndex, Object, RectangleId, TextId};
-use crate::draw::Draw;
-use crate::object::circle::circle;
+use crate::animation::{AnimBuilder, AnimationGroup, RunCommand, TargetAction};
+use crate::animation::{TimedCommand, Updater, UpdaterId, UserCommand};
+use crate::appearance::{GetOpacity, SetOpacity};
+use crate::arena::{AddObject, ArcId, Arena, CircleId, EllipseId, GroupId, HasArena, Id, Index};
+use crate::arena::{LineId, MathTexId, NodeArena, NodeIndex, Object, PolygonId, RectangleId};
+use crate::arena::{TextId, VPathId, ValueTrackerId};
+use crate::draw::{Backend, Draw, Nested, Transform};
+use crate::geom::{Point, SetPosition};
+use crate::import::{svg, SvgError, TexError, TexRenderer};
+use crate::object::arc::{annulus, arc, sector};
+use crate::object::circle::{circle, dot};
+use crate::object::ellipse::ellipse;
+use crate::object::group::group;
+use crate::object::line::{arrow, line};
+use crate::object::math_tex::math_tex;
+use crate::object::polygon::{polygon, regular_polygon};
 use crate::object::rectangle::rectangle;
 use crate::object::text::text;
+use crate::object::value_tracker::value_tracker;
+use crate::object::vpath::{svg_paths, vpath};
+use crate::object::VPathBuilder;
 
 // use std::slice::IterMut;
+use std::rc::Rc;
 
 use nannou::geom::Rect;
 
 /// Collection of resources used by animation
 pub struct Resource {
     window: Rect,
+    tex: TexRenderer,
 }
 
 impl Resource {
     pub fn new(window: Rect) -> Self {
-        Self { window }
+        Self {
+            window,
+            tex: TexRenderer::new(),
+        }
+    }
+    pub fn tex(&self) -> &TexRenderer {
+        &self.tex
     }
     pub fn edge_upper(&self) -> f32 {
         self.window.y.end
@@ -37,8 +59,11 @@ impl Resource {
 pub struct Scene {
     pub commands: Vec<TimedCommand>,
     objects: Arena<Object>,
+    initial: Option<Arena<Object>>, // Objects before any command is processed
     prev_command: usize,
+    time: f32,
     resource: Resource,
+    updaters: Vec<Updater>, // Run after commands on every update
 }
 
 impl Scene {
@@ -46,27 +71,185 @@ impl Scene {
         let mut scene = Scene {
             commands: Vec::new(),
             objects: Arena::new(),
+            initial: None,
             prev_command: 0,
+            time: 0.0,
             resource: Resource::new(window),
+            updaters: Vec::new(),
         };
         scene.wait(0.0); // Put dummy command at the beginning
         scene
     }
 
+    /// Toolchain and cache used for `math_tex`
+    pub fn set_tex_renderer(&mut self, renderer: TexRenderer) {
+        self.resource.tex = renderer;
+    }
+
     pub fn play_many(&mut self, target_actions: Vec<TargetAction>) -> AnimBuilder {
         AnimBuilder::new(self, target_actions)
     }
 
+    /// Advance scene to `time`. Going backwards replays the commands from
+    /// the beginning, so any time can be visited in any order.
     pub fn update(&mut self, time: f32) {
+        if self.initial.is_none() {
+            self.initial = Some(self.objects.clone());
+        }
+        if time < self.time {
+            self.reset();
+        }
+        let dt = time - self.time;
+        self.time = time;
         // dbg!(&self.prev_command);
         self.prev_command =
             self.commands
                 .process(self.prev_command, time, &mut self.objects, &self.resource);
+        self.run_updaters(dt);
+    }
+
+    /// Run `func` on `object` at every update from the end of the commands
+    /// so far, like manim's `add_updater`. Other objects are read through
+    /// the scene, and `dt` is the time since the previous update, which
+    /// covers the whole time from the start after seeking backwards.
+    ///
+    /// ```ignore
+    /// scene.add_updater(label, move |label, scene, _dt| {
+    ///     let position = scene.get(&dot).unwrap().position();
+    ///     label.move_to(position.x, position.y + 20.0);
+    /// });
+    /// ```
+    pub fn add_updater<T, F>(&mut self, object: T, func: F) -> UpdaterId
+    where
+        T: Into<Index> + Copy,
+        F: Fn(&mut Object, &Scene, f32) + 'static,
+    {
+        let id = UpdaterId(self.updaters.len());
+        let start = self.commands.end_time();
+        let target = Id(object.into());
+        self.updaters
+            .push(Updater::new(id, target, Rc::new(func), start));
+        id
+    }
+
+    /// Rebuild `object` at every update, like manim's `always_redraw`.
+    /// Opacity is kept from the object, which is shown like any other.
+    ///
+    /// ```ignore
+    /// let t = scene.value_tracker(0.0);
+    /// let bar = scene.rectangle();
+    /// scene.always_redraw(bar, move |scene| {
+    ///     let mut bar = rectangle();
+    ///     bar.set_width(t.get_value(scene).max(1.0));
+    ///     bar
+    /// });
+    /// scene.show(bar);
+    /// scene.play(t.set_value(200.0)).rate_func(QUAD);
+    /// ```
+    pub fn always_redraw<T, F>(&mut self, object: T, build: F) -> UpdaterId
+    where
+        T: Into<Index> + Copy,
+        F: Fn(&Scene) -> Object + 'static,
+    {
+        self.add_updater(object, move |object, scene, _dt| {
+            let mut fresh = build(scene).inner;
+            *fresh.opacity_mut() = object.inner.opacity_mut().clone();
+            object.inner = fresh;
+        })
     }
 
-    pub fn draw(&self, nannou_draw: nannou::Draw) {
-        for (_idx, object) in &self.objects {
-            object.draw(nannou_draw.clone());
+    /// Stop running an updater from the end of the commands so far
+    pub fn remove_updater(&mut self, id: UpdaterId) {
+        let time = self.commands.end_time();
+        if let Some(updater) = self.updaters.iter_mut().find(|u| u.id == id) {
+            updater.remove_at(time);
+        }
+    }
+
+    /// Pause updaters of `targets` between `from` and `to`
+    pub(crate) fn suspend_updaters(&mut self, targets: &[Id], from: f32, to: f32) {
+        self.updaters
+            .iter_mut()
+            .filter(|updater| targets.contains(&updater.target))
+            .for_each(|updater| updater.suspend(from, to));
+    }
+
+    // Each updater sees the changes of the ones added before it
+    fn run_updaters(&mut self, dt: f32) {
+        for i in 0..self.updaters.len() {
+            let updater = &self.updaters[i];
+            if !updater.is_active(self.time) {
+                continue;
+            }
+            let (target, func) = (updater.target, updater.func.clone());
+            if let Some(mut object) = self.objects.get(target.0).cloned() {
+                func(&mut object, self, dt);
+                if let Some(current) = self.objects.get_mut(target.0) {
+                    *current = object;
+                }
+            }
+        }
+    }
+
+    /// Evaluate the scene at `time` independently of previous updates
+    pub fn state_at(&mut self, time: f32) -> &Arena<Object> {
+        self.reset();
+        self.update(time);
+        &self.objects
+    }
+
+    /// Restore objects and commands to the state before the first update
+    pub fn reset(&mut self) {
+        if let Some(initial) = &self.initial {
+            self.objects = initial.clone();
+        }
+        self.commands.reset();
+        self.prev_command = 0;
+        self.time = 0.0;
+    }
+
+    // Objects start where they are when added, before any command runs.
+    // Added after an update, the scene is first rewound to the start, so
+    // seeking backwards keeps them, and replayed on the next update.
+    fn add_object(&mut self, object: Object) -> Id {
+        self.reset();
+        self.initial = None;
+        Id::new(self.objects.insert(object))
+    }
+
+    /// Time of the last update
+    pub fn time(&self) -> f32 {
+        self.time
+    }
+
+    pub fn objects(&self) -> &Arena<Object> {
+        &self.objects
+    }
+
+    /// Placement of object in the scene, composed of all its ancestors
+    pub fn world_transform(&self, id: &Id) -> Transform {
+        self.objects.world_transform(*id)
+    }
+
+    pub fn draw<B: Backend>(&self, backend: &mut B) {
+        for (idx, object) in &self.objects {
+            if object.parent.is_none() {
+                self.draw_node(idx, &Transform::identity(), 1.0, backend);
+            }
+        }
+    }
+
+    // Draw object and its children inside the space of its parent
+    fn draw_node<B: Backend>(&self, idx: Index, parent: &Transform, alpha: f32, backend: &mut B) {
+        if let Some(object) = self.objects.get(idx) {
+            object.draw(&mut Nested::new(backend, *parent, alpha));
+            if object.is_visible() {
+                let transform = object.transform().then(parent);
+                let alpha = alpha * object.alpha();
+                for child in object.children.iter() {
+                    self.draw_node(*child, &transform, alpha, backend);
+                }
+            }
         }
     }
 }
@@ -81,6 +264,9 @@ impl UserCommand for Scene {
     fn wait(&mut self, time: f32) {
         self.commands.wait(time);
     }
+    fn play_group(&mut self, group: AnimationGroup) {
+        self.commands.play(group.animations());
+    }
     fn show<T>(&mut self, object: T)
     where
         T: Into<Index> + Sized + Copy,
@@ -103,7 +289,7 @@ impl UserCommand for Scene {
 
 impl HasArena for Scene {
     fn add(&mut self, object: Object) -> Id {
-        Id::new(self.objects.insert(object))
+        self.add_object(object)
     }
     fn get_mut(&mut self, index: &Id) -> Option<&mut Object> {
         self.objects.get_mut(index.0)
@@ -116,28 +302,386 @@ impl HasArena for Scene {
             .get(index.0)
             .and_then(|object| object.parent.and_then(|parent| self.objects.get(parent)))
     }
+    // Queued, so the child is placed where earlier commands leave it
+    fn add_child(&mut self, parent: &Id, child: &Id) {
+        self.commands.attach(*parent, *child);
+    }
 }
 
 impl AddObject for Scene {
     fn circle(&mut self) -> CircleId {
-        let index = self.objects.add(circle());
+        let index = self.add_object(circle());
         CircleId(index.0)
     }
     fn rectangle(&mut self) -> RectangleId {
-        let index = self.objects.add(rectangle());
+        let index = self.add_object(rectangle());
         RectangleId(index.0)
     }
     fn text(&mut self, content: &str) -> TextId {
-        let index = self.objects.add(text(content));
+        let index = self.add_object(text(content));
         TextId(index.0)
     }
+    fn try_math_tex(&mut self, parts: &[&str]) -> Result<MathTexId, TexError> {
+        let object = math_tex(parts, &self.resource.tex)?;
+        let index = self.add_object(object);
+        Ok(MathTexId(index.0))
+    }
+    fn dot(&mut self) -> CircleId {
+        let index = self.add_object(dot());
+        CircleId(index.0)
+    }
+    fn line(&mut self, start: Point, end: Point) -> LineId {
+        let index = self.add_object(line(start, end));
+        LineId(index.0)
+    }
+    fn arrow(&mut self, start: Point, end: Point) -> LineId {
+        let index = self.add_object(arrow(start, end));
+        LineId(index.0)
+    }
+    fn polygon(&mut self, vertices: &[Point]) -> PolygonId {
+        let index = self.add_object(polygon(vertices));
+        PolygonId(index.0)
+    }
+    fn regular_polygon(&mut self, sides: usize) -> PolygonId {
+        let index = self.add_object(regular_polygon(sides));
+        PolygonId(index.0)
+    }
+    fn arc(&mut self, angle: f32) -> ArcId {
+        let index = self.add_object(arc(angle));
+        ArcId(index.0)
+    }
+    fn sector(&mut self, angle: f32) -> ArcId {
+        let index = self.add_object(sector(angle));
+        ArcId(index.0)
+    }
+    fn annulus(&mut self, inner_ratio: f32) -> ArcId {
+        let index = self.add_object(annulus(inner_ratio));
+        ArcId(index.0)
+    }
+    fn ellipse(&mut self) -> EllipseId {
+        let index = self.add_object(ellipse());
+        EllipseId(index.0)
+    }
+    fn path(&mut self, builder: VPathBuilder) -> VPathId {
+        let index = self.add_object(vpath(builder));
+        VPathId(index.0)
+    }
+    fn group(&mut self) -> GroupId {
+        let index = self.add_object(group());
+        GroupId(index.0)
+    }
+    fn try_svg(&mut self, file: &str) -> Result<GroupId, SvgError> {
+        let shapes = svg::load(file)?;
+        let index = self.add_object(group());
+        for path in svg_paths(&shapes) {
+            let child = self.add_object(path);
+            self.objects.attach(index, child);
+        }
+        // Drawing is centered, and hidden until shown like any other object
+        if let Some(drawing) = self.objects.get_mut(index.0) {
+            drawing.move_to(0.0, 0.0);
+            drawing.hide();
+        }
+        Ok(GroupId(index.0))
+    }
+    fn value_tracker(&mut self, value: f32) -> ValueTrackerId {
+        let index = self.add_object(value_tracker(value));
+        ValueTrackerId(index.0)
+    }
 }
 
-/// Trait to provide user-facing function for making animations
+/// Trait to provide user-facing function for making animations.
+/// Each implementor describes one animation, built into a fresh `Scene`.
 pub trait Construct {
-    fn construct(&mut self);
+    fn construct(&self, scene: &mut Scene);
+}
+
+/// Named collection of animations, selectable at runtime
+#[derive(Default)]
+pub struct Registry {
+    entries: Vec<(String, Box<dyn Construct>)>,
+}
+
+impl Registry {
+    pub fn new() -> Self {
+        Self {
+            entries: Vec::new(),
+        }
+    }
+    /// Register `construct` under `name`, replacing any previous one with same name
+    pub fn add<C>(mut self, name: &str, construct: C) -> Self
+    where
+        C: Construct + 'static,
+    {
+        self.entries.retain(|(n, _)| n != name);
+        self.entries.push((String::from(name), Box::new(construct)));
+        self
+    }
+    /// Names of registered scenes, in order of registration
+    pub fn names(&self) -> Vec<&str> {
+        self.entries.iter().map(|(name, _)| name.as_str()).collect()
+    }
+    /// First registered scene, used when no name is given
+    pub fn default_name(&self) -> Option<&str> {
+        self.entries.first().map(|(name, _)| name.as_str())
+    }
+    pub fn get(&self, name: &str) -> Option<&dyn Construct> {
+        self.entries
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, construct)| construct.as_ref())
+    }
+    /// Construct scene `name` into a fresh `Scene` fitting `window`
+    pub fn build(&self, name: &str, window: Rect) -> Option<Scene> {
+        self.get(name).map(|construct| {
+            let mut scene = scene(window);
+            construct.construct(&mut scene);
+            scene
+        })
+    }
 }
 
 pub fn scene(window: Rect) -> Scene {
     Scene::new(window)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::animation::Actionable;
+
+    struct Empty;
+    impl Construct for Empty {
+        fn construct(&self, _scene: &mut Scene) {}
+    }
+
+    struct Blink;
+    impl Construct for Blink {
+        fn construct(&self, scene: &mut Scene) {
+            let c = scene.circle();
+            scene.play(c.fade_in()).run_time(2.0);
+        }
+    }
+
+    #[test]
+    fn build_by_name() {
+        let registry = Registry::new().add("Empty", Empty).add("Blink", Blink);
+        let window = Rect::from_w_h(640.0, 480.0);
+
+        assert_eq!(registry.names(), vec!["Empty", "Blink"]);
+        assert_eq!(registry.default_name(), Some("Empty"));
+        assert!(registry.build("Missing", window).is_none());
+
+        let blink = registry.build("Blink", window).unwrap();
+        assert_eq!(blink.commands.end_time(), 2.0);
+        // Every build starts from a fresh scene
+        let blink = registry.build("Blink", window).unwrap();
+        assert_eq!(blink.commands.end_time(), 2.0);
+    }
+
+    #[test]
+    fn group_composes_transforms() {
+        use crate::animation::UserCommand;
+        use crate::consts::RIGHT;
+        use crate::geom::{point_at, GetPosition};
+
+        let mut scene = scene(Rect::from_w_h(640.0, 480.0));
+        let a = scene.circle();
+        let b = scene.circle();
+        scene.act(a.move_to(point_at(100.0, 0.0)));
+        scene.act(b.move_to(point_at(200.0, 0.0)));
+        let g = scene.group().add(&mut scene, a).add(&mut scene, b);
+
+        // Group is centered on where its children are once added, and
+        // they keep their place
+        scene.update(0.0);
+        let world = |scene: &Scene, id: Index| scene.world_transform(&Id(id)).position;
+        assert_eq!(
+            scene.get(&Id(g.0)).unwrap().position(),
+            point_at(150.0, 0.0)
+        );
+        assert_eq!(world(&scene, a.0), point_at(100.0, 0.0));
+
+        scene.play(g.move_by(RIGHT * 50.0));
+        scene.play(g.scale_by(2.0));
+        scene.play(a.move_by(RIGHT * 10.0));
+        scene.update(3.0);
+        // Children move within the scaled space of the group
+        assert_eq!(world(&scene, a.0), point_at(120.0, 0.0));
+        assert_eq!(world(&scene, b.0), point_at(300.0, 0.0));
+    }
+
+    #[test]
+    fn split_text_into_words_and_glyphs() {
+        use crate::geom::point_at;
+        use crate::object::Object as Inner;
+        use crate::path::Bounds;
+
+        let mut scene = scene(Rect::from_w_h(640.0, 480.0));
+        let t = scene.text("ab c");
+        let centers: Vec<Point> = match &scene.get(&Id(t.0)).unwrap().inner {
+            Inner::Text(text) => text
+                .glyph_paths()
+                .iter()
+                .map(|glyph| Bounds::of_path(glyph).center().into())
+                .collect(),
+            _ => unreachable!(),
+        };
+        scene.act(t.move_to(point_at(40.0, 10.0)));
+        let split = t.split(&mut scene);
+        scene.play(split.glyphs[2].move_by(point_at(0.0, 20.0)));
+        assert_eq!((split.words.len(), split.glyphs.len()), (2, 3));
+        assert!(scene.get(&Id(t.0)).is_some());
+
+        // Text is split where the move before left it
+        scene.update(0.0);
+        assert!(scene.get(&Id(t.0)).is_none());
+        let word = scene.get(&Id(split.words[0].0)).unwrap();
+        assert_eq!(word.children.len(), 2);
+        let close = |a: Point, b: Point| (a - b).x.abs() < 1e-3 && (a - b).y.abs() < 1e-3;
+        let glyph =
+            |scene: &Scene, i: usize| scene.world_transform(&Id(split.glyphs[i].0)).position;
+        for (i, center) in centers.iter().enumerate() {
+            assert!(close(glyph(&scene, i), *center + point_at(40.0, 10.0)));
+        }
+
+        // Glyphs are animated after the split
+        scene.update(1.0);
+        assert!(close(glyph(&scene, 2), centers[2] + point_at(40.0, 30.0)));
+        assert!(close(glyph(&scene, 0), centers[0] + point_at(40.0, 10.0)));
+    }
+
+    #[test]
+    fn updaters_follow_other_objects() {
+        use crate::consts::RIGHT;
+        use crate::geom::{point_at, GetPosition, SetPosition};
+
+        let mut scene = scene(Rect::from_w_h(640.0, 480.0));
+        let dot = scene.dot();
+        let label = scene.circle();
+        let follow = scene.add_updater(label, move |label, scene, _dt| {
+            let position = scene.get(&Id(dot.0)).unwrap().position();
+            label.move_to(position.x, position.y + 20.0);
+        });
+        scene.play(dot.move_by(RIGHT * 100.0));
+        scene.play(label.move_by(RIGHT * 100.0)).suspend_updaters();
+        scene.remove_updater(follow);
+        scene.play(dot.move_by(RIGHT * 100.0));
+        let label = Id(label.0);
+
+        scene.update(0.5);
+        assert_eq!(scene.get(&label).unwrap().position(), point_at(50.0, 20.0));
+
+        // Animation of the label itself takes over while suspended
+        scene.update(1.0);
+        scene.update(1.5);
+        assert_eq!(scene.get(&label).unwrap().position(), point_at(100.0, 20.0));
+
+        // Removed updater leaves the label where the animation ended
+        scene.update(2.5);
+        assert_eq!(scene.get(&label).unwrap().position(), point_at(150.0, 20.0));
+        assert_eq!(
+            scene.get(&Id(dot.0)).unwrap().position(),
+            point_at(150.0, 0.0)
+        );
+    }
+
+    #[test]
+    fn value_trackers_drive_updaters() {
+        use crate::consts::QUAD;
+        use crate::ease::EaseType;
+        use crate::geom::{point_at, GetDimension, GetPosition, SetDimension};
+
+        let mut scene = scene(Rect::from_w_h(640.0, 480.0));
+        let t = scene.value_tracker(0.0);
+        let bar = scene.rectangle();
+        scene.always_redraw(bar, move |scene| {
+            let mut bar = rectangle();
+            bar.set_width(t.get_value(scene).max(1.0));
+            bar
+        });
+        scene.show(bar);
+        scene.play(t.set_value(100.0)).rate_func(QUAD);
+        scene.play(t.increment_value(-50.0));
+        scene
+            .play(t.set_point(point_at(10.0, 20.0)))
+            .rate_func(EaseType::custom(|t| t));
+        let bar = Id(bar.0);
+
+        scene.update(0.5);
+        let expected = QUAD.calculate(0.5) * 100.0;
+        assert_eq!(t.get_value(&scene), expected);
+        assert_eq!(scene.get(&bar).unwrap().width(), expected);
+        assert!(scene.get(&bar).unwrap().is_visible());
+
+        scene.update(1.5);
+        assert_eq!(t.get_value(&scene), 75.0);
+        scene.update(3.0);
+        assert_eq!(t.get_value(&scene), 50.0);
+        assert_eq!(t.get_point(&scene), point_at(10.0, 20.0));
+        assert!(!scene.get(&Id(t.0)).unwrap().is_visible());
+    }
+
+    #[test]
+    fn group_colors_its_members() {
+        use crate::appearance::{GetColor, Paint};
+        use crate::consts::{BLUE_C, RED_D};
+
+        let mut scene = scene(Rect::from_w_h(640.0, 480.0));
+        let a = scene.circle();
+        let b = scene.rectangle();
+        let inner = scene.group().add(&mut scene, b);
+        let g = scene.group().add(&mut scene, a).add(&mut scene, inner);
+        scene.act(g.set_fill(BLUE_C));
+        scene.play(g.set_color(RED_D));
+        let (a, b) = (Id(a.0), Id(b.0));
+
+        scene.update(0.0);
+        assert_eq!(scene.get(&b).unwrap().fill_paint(), Paint::Solid(BLUE_C));
+        scene.update(1.0);
+        for id in [a, b].iter() {
+            let object = scene.get(id).unwrap();
+            assert_eq!(object.fill_paint(), Paint::Solid(RED_D));
+            assert_eq!(object.stroke_paint(), Paint::Solid(RED_D));
+        }
+    }
+
+    #[test]
+    fn typesetting_errors_are_returned() {
+        let mut scene = scene(Rect::from_w_h(640.0, 480.0));
+        let dir = std::env::temp_dir().join(format!("manim_rs_scene_tex_{}", std::process::id()));
+        scene.set_tex_renderer(TexRenderer::new().latex("missing-latex").cache_dir(&dir));
+
+        assert!(scene.try_math_tex(&["x^2"]).is_err());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn import_errors_are_returned() {
+        let mut scene = scene(Rect::from_w_h(640.0, 480.0));
+        assert!(scene.try_svg("missing/drawing.svg").is_err());
+    }
+
+    #[test]
+    fn objects_added_after_an_update_survive_seeking() {
+        use crate::geom::{point_at, GetPosition};
+
+        let mut scene = scene(Rect::from_w_h(640.0, 480.0));
+        let a = scene.circle();
+        scene.play(a.move_to(point_at(100.0, 0.0)));
+        scene.update(1.0);
+        let b = scene.circle();
+        scene.play(b.move_to(point_at(0.0, 50.0)));
+        let position = |scene: &Scene, id: Index| scene.get(&Id(id)).unwrap().position();
+
+        scene.update(2.0);
+        assert_eq!(position(&scene, a.0), point_at(100.0, 0.0));
+        assert_eq!(position(&scene, b.0), point_at(0.0, 50.0));
+        scene.update(0.5);
+        assert_eq!(position(&scene, b.0), point_at(0.0, 0.0));
+        let x = position(&scene, a.0).x;
+        assert!(x > 0.0 && x < 100.0);
+        scene.update(2.0);
+        assert_eq!(position(&scene, b.0), point_at(0.0, 50.0));
+    }
+}